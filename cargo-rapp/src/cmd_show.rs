@@ -47,7 +47,7 @@ impl Show {
 
         // Run
         info!("Run {}", config.name);
-        viewer.run()?;
+        viewer.run(&config.app_dir)?;
 
         // println!("set env var RAPP_RUNNER_STOP to false");
         // println!("Run cargo build - with target dir");
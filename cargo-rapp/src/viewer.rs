@@ -1,6 +1,7 @@
 use crate::{config::Config, error::RappError};
 use anyhow::{bail, Result};
 use log::debug;
+use rapp::assets::ASSETS_DIR_VAR;
 use serde::{Deserialize, Serialize};
 use std::{
     fs,
@@ -36,7 +37,7 @@ impl Viewer {
         Ok(viewer)
     }
 
-    pub(crate) fn run(&self) -> Result<()> {
+    pub(crate) fn run(&self, app_dir: &Path) -> Result<()> {
         let bin = if let Some(bin) = &self.bin {
             if !bin.exists() {
                 bail!(RappError::Other(format!(
@@ -53,7 +54,9 @@ impl Viewer {
         };
 
         // Run
-        let output = Command::new(bin).output()?;
+        let output = Command::new(bin)
+            .env(ASSETS_DIR_VAR, app_dir.join("assets"))
+            .output()?;
         if output.status.success() {
             println!("{}", String::from_utf8(output.stdout)?);
         } else {
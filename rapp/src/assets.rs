@@ -0,0 +1,250 @@
+//! Images from the `assets/` directory of the app crate.
+//!
+//! `include_asset!` embeds a file in the app when it is built. `ImageSource::Asset` reads the
+//! file from disk when it is first shown: from the directory `cargo rapp` passes to the viewer
+//! in `RAPP_ASSETS_DIR`, else from `assets/` of the crate that runs, as in its tests.
+//!
+//! Images are decoded as a task and kept for as long as the app runs, also when decoding
+//! failed, until `reload` is called. Until then drawables show a placeholder. The test driver
+//! decodes them in `Driver::run_tasks`.
+
+use crate::{
+    layout::Size,
+    task::{self, Task, TaskState},
+};
+use resvg::{tiny_skia, usvg};
+use std::{cell::RefCell, collections::HashMap, fmt::Display, path::PathBuf, sync::Arc};
+
+/// The environment variable with the assets directory of the app
+pub const ASSETS_DIR_VAR: &str = "RAPP_ASSETS_DIR";
+
+/// Pixels for each point when an SVG is rendered, so it stays sharp on the phone of the viewer
+const SVG_SCALE: f32 = 2.;
+
+type Decoding = Task<Result<Arc<DecodedImage>, String>>;
+
+thread_local! {
+    // By path within the assets directory
+    static IMAGES: RefCell<HashMap<String, Decoding>> = RefCell::new(HashMap::new());
+}
+
+/// Embeds a file of the `assets/` directory of the crate in the app
+#[macro_export]
+macro_rules! include_asset {
+    ($path:literal) => {
+        $crate::assets::ImageSource::Embedded {
+            path: $path,
+            bytes: include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/", $path)),
+        }
+    };
+}
+
+/// Where an image comes from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImageSource {
+    /// A path within the assets directory, read from disk
+    Asset(String),
+    /// A file embedded with `include_asset!`
+    Embedded {
+        path: &'static str,
+        bytes: &'static [u8],
+    },
+}
+
+impl ImageSource {
+    /// The path within the assets directory
+    pub fn path(&self) -> &str {
+        match self {
+            ImageSource::Asset(path) => path,
+            ImageSource::Embedded { path, .. } => path,
+        }
+    }
+
+    fn read(&self) -> Result<Vec<u8>, String> {
+        match self {
+            ImageSource::Asset(path) => {
+                std::fs::read(dir().join(path)).map_err(|error| format!("{path}: {error}"))
+            }
+            ImageSource::Embedded { bytes, .. } => Ok(bytes.to_vec()),
+        }
+    }
+}
+
+impl From<&str> for ImageSource {
+    fn from(path: &str) -> Self {
+        ImageSource::Asset(path.to_string())
+    }
+}
+
+impl From<String> for ImageSource {
+    fn from(path: String) -> Self {
+        ImageSource::Asset(path)
+    }
+}
+
+/// Where `ImageSource::Asset` paths are read from
+pub fn dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(ASSETS_DIR_VAR) {
+        return dir.into();
+    }
+    let mut dir: PathBuf = std::env::var_os("CARGO_MANIFEST_DIR")
+        .unwrap_or_default()
+        .into();
+    dir.push("assets");
+    dir
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImageStatus {
+    Loading,
+    /// Decoded, with its size in points
+    Ready(Size),
+    /// With what went wrong
+    Failed(String),
+}
+
+/// Pixels that are not premultiplied, row by row
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedImage {
+    /// In points
+    pub size: Size,
+    /// Width and height in pixels
+    pub pixels: [usize; 2],
+    pub rgba: Vec<u8>,
+}
+
+/// Where decoding the image is at. The first call starts decoding it.
+pub fn load(source: &ImageSource) -> ImageStatus {
+    let decoding = IMAGES.with(|images| {
+        images
+            .borrow_mut()
+            .entry(source.path().to_string())
+            .or_insert_with(|| {
+                let source = source.clone();
+                // Other pages may show it too
+                task::detached(|| {
+                    task::spawn_blocking(move || {
+                        decode(source.path(), &source.read()?).map(Arc::new)
+                    })
+                })
+            })
+            .clone()
+    });
+    let state = decoding.state();
+    match &*state {
+        TaskState::Running => ImageStatus::Loading,
+        TaskState::Done(Ok(image)) => ImageStatus::Ready(image.size),
+        TaskState::Done(Err(message)) | TaskState::Failed(message) => {
+            ImageStatus::Failed(message.clone())
+        }
+        TaskState::Cancelled => ImageStatus::Failed("decoding was cancelled".to_string()),
+    }
+}
+
+/// Forgets the image at `path`, so the next `load` decodes it again, as when it failed
+/// because the file was not there yet
+pub fn reload(path: &str) {
+    let decoding = IMAGES.with(|images| images.borrow_mut().remove(path));
+    if let Some(decoding) = decoding {
+        decoding.cancel();
+    }
+}
+
+/// The pixels of an image that `load` decoded
+pub fn decoded(source: &ImageSource) -> Option<Arc<DecodedImage>> {
+    IMAGES.with(|images| {
+        let images = images.borrow();
+        let state = images.get(source.path())?.state();
+        state.done()?.as_ref().ok().cloned()
+    })
+}
+
+/// Decodes PNG and JPEG files, and renders SVG files. The extension of `path` tells which it is.
+pub fn decode(path: &str, bytes: &[u8]) -> Result<DecodedImage, String> {
+    let error = |error: &dyn Display| format!("{path}: {error}");
+    if path.to_lowercase().ends_with(".svg") {
+        let tree =
+            usvg::Tree::from_data(bytes, &usvg::Options::default()).map_err(|e| error(&e))?;
+        let size = tree.size();
+        let width = (size.width() * SVG_SCALE).ceil() as u32;
+        let height = (size.height() * SVG_SCALE).ceil() as u32;
+        let mut pixmap =
+            tiny_skia::Pixmap::new(width, height).ok_or_else(|| error(&"the image is empty"))?;
+        let transform = tiny_skia::Transform::from_scale(SVG_SCALE, SVG_SCALE);
+        resvg::render(&tree, transform, &mut pixmap.as_mut());
+        let rgba = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let color = pixel.demultiply();
+                [color.red(), color.green(), color.blue(), color.alpha()]
+            })
+            .collect();
+        Ok(DecodedImage {
+            size: Size::new(size.width(), size.height()),
+            pixels: [width as usize, height as usize],
+            rgba,
+        })
+    } else {
+        let image = image::load_from_memory(bytes)
+            .map_err(|e| error(&e))?
+            .to_rgba8();
+        let (width, height) = image.dimensions();
+        Ok(DecodedImage {
+            size: Size::new(width as f32, height as f32),
+            pixels: [width as usize, height as usize],
+            rgba: image.into_raw(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load, reload, ImageSource, ImageStatus};
+    use crate::{layout::Size, task};
+
+    const SVG: &[u8] = br#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="20">
+        <rect width="10" height="10" fill="red"/>
+    </svg>"#;
+
+    #[test]
+    fn decodes_as_a_task() {
+        task::set_manual(true);
+        let mut png = vec![];
+        image::RgbaImage::new(3, 2)
+            .write_to(
+                &mut std::io::Cursor::new(&mut png),
+                image::ImageOutputFormat::Png,
+            )
+            .unwrap();
+        let png = ImageSource::Embedded {
+            path: "pixels.png",
+            bytes: png.leak(),
+        };
+        let svg = ImageSource::Embedded {
+            path: "logo.svg",
+            bytes: SVG,
+        };
+        let missing = ImageSource::from("missing.jpg");
+
+        assert_eq!(load(&png), ImageStatus::Loading);
+        assert_eq!(load(&svg), ImageStatus::Loading);
+        assert_eq!(load(&missing), ImageStatus::Loading);
+        task::run_pending();
+
+        assert_eq!(load(&png), ImageStatus::Ready(Size::new(3., 2.)));
+        assert_eq!(load(&svg), ImageStatus::Ready(Size::new(10., 20.)));
+        let svg = super::decoded(&svg).unwrap();
+        assert_eq!(svg.pixels, [20, 40]);
+        assert_eq!(svg.rgba[..4], [255, 0, 0, 255]);
+        assert!(matches!(load(&missing), ImageStatus::Failed(_)));
+        // Not read again in every frame
+        assert!(matches!(load(&missing), ImageStatus::Failed(_)));
+        assert_eq!(task::run_pending(), 0);
+
+        reload("missing.jpg");
+        assert_eq!(load(&missing), ImageStatus::Loading);
+        assert_eq!(task::run_pending(), 1);
+        assert!(matches!(load(&missing), ImageStatus::Failed(_)));
+    }
+}
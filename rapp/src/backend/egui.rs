@@ -0,0 +1,501 @@
+//! The egui backend, used by the runner
+
+use super::{Backend, ButtonColors, Interaction, Pointer, Sense, TextFieldRun, TextRun};
+use crate::{
+    assets::{self, ImageSource, ImageStatus},
+    color::Color,
+    drawables::text::{FontWeight, TextAlign},
+    gestures::{Gesture, GestureInput, GestureKind, Recognizer, LONG_PRESS_SECONDS},
+    layout::{Rect, Size},
+    style,
+};
+use egui::{
+    epaint, pos2,
+    text::{LayoutJob, TextFormat},
+    vec2, Align, Color32, ColorImage, FontId, Galley, Id, Key, Layout, Response, RichText,
+    Rounding, Spinner, Stroke, Style, TextBuffer, TextEdit, TextStyle, TextureHandle,
+    TextureOptions, Ui, WidgetText,
+};
+use std::{
+    collections::HashMap,
+    ops::Range,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// How far alt+scroll turns a pinch, in radians per point scrolled. The viewer has no second
+/// finger, ctrl+scroll zooms and alt+scroll rotates instead.
+pub const RADIANS_PER_SCROLL: f32 = 0.01;
+
+/// Textures of decoded images by their path. They are kept in the memory of the egui context,
+/// so the runner uploads each image once.
+#[derive(Clone, Default)]
+struct Textures(Arc<Mutex<HashMap<String, TextureHandle>>>);
+
+/// Draws into an egui `Ui`
+pub struct EguiBackend<'a> {
+    ui: &'a mut Ui,
+    opacity: f32,
+}
+
+impl<'a> EguiBackend<'a> {
+    pub fn new(ui: &'a mut Ui) -> Self {
+        Self { ui, opacity: 1. }
+    }
+
+    /// The color as painted with the current opacity
+    fn color(&self, color: Color) -> Color32 {
+        Color32::from(color).linear_multiply(self.opacity)
+    }
+
+    fn texture(&self, source: &ImageSource) -> Option<TextureHandle> {
+        let ctx = self.ui.ctx();
+        let textures = ctx.data_mut(|data| {
+            data.get_temp_mut_or_default::<Textures>(Id::new("rapp_textures"))
+                .clone()
+        });
+        let mut textures = textures.0.lock().unwrap();
+        if let Some(texture) = textures.get(source.path()) {
+            return Some(texture.clone());
+        }
+        let image = assets::decoded(source)?;
+        let pixels = ColorImage::from_rgba_unmultiplied(image.pixels, &image.rgba);
+        let texture = ctx.load_texture(source.path(), pixels, TextureOptions::LINEAR);
+        textures.insert(source.path().to_string(), texture.clone());
+        Some(texture)
+    }
+
+    /// What the response means for a rapp drawable, including long presses
+    fn interaction(&self, response: Response) -> Interaction {
+        let ui = &self.ui;
+        // A press that already fired a long press does not also count as a click
+        let id = response.id.with("long_press");
+        let fired = ui
+            .data_mut(|data| data.get_temp::<bool>(id))
+            .unwrap_or(false);
+
+        let mut long_pressed = false;
+        let mut clicked = false;
+        // egui still reports the press on the frame of the release
+        let down = response.is_pointer_button_down_on() && ui.input(|i| i.pointer.any_down());
+        if down {
+            let (time, start) = ui.input(|i| (i.time, i.pointer.press_start_time()));
+            let held = start.map(|start| time - start).unwrap_or_default();
+            if held >= LONG_PRESS_SECONDS {
+                if !fired {
+                    long_pressed = true;
+                    ui.data_mut(|data| data.insert_temp(id, true));
+                }
+            } else {
+                // no input arrives while the pointer is held still
+                let remaining = Duration::from_secs_f64(LONG_PRESS_SECONDS - held);
+                ui.ctx().request_repaint_after(remaining);
+            }
+        } else {
+            clicked = response.clicked() && !fired;
+            if fired {
+                ui.data_mut(|data| data.remove::<bool>(id));
+            }
+        }
+
+        Interaction {
+            rect: response.rect.into(),
+            enabled: response.enabled,
+            hovered: response.hovered(),
+            clicked,
+            long_pressed,
+            pointer: response.interact_pointer_pos().map(|pos| (pos.x, pos.y)),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<TextAlign> for Align {
+    fn from(align: TextAlign) -> Self {
+        match align {
+            TextAlign::Left => Align::LEFT,
+            TextAlign::Center => Align::Center,
+            TextAlign::Right => Align::RIGHT,
+        }
+    }
+}
+
+impl From<Sense> for egui::Sense {
+    fn from(sense: Sense) -> Self {
+        match sense {
+            Sense::Hover => egui::Sense::hover(),
+            Sense::Click => egui::Sense::click(),
+            Sense::Drag => egui::Sense::click_and_drag(),
+            Sense::Scroll => egui::Sense::hover(),
+        }
+    }
+}
+
+fn layout_job(text: &TextRun, style: &Style, wrap_width: f32, opacity: f32) -> LayoutJob {
+    let size = text
+        .size
+        .unwrap_or_else(|| TextStyle::Body.resolve(style).size);
+    let color = text
+        .color
+        .map(Into::into)
+        .unwrap_or_else(|| match text.weight {
+            FontWeight::Light => style.visuals.weak_text_color(),
+            FontWeight::Regular => style.visuals.text_color(),
+            FontWeight::Bold => style.visuals.strong_text_color(),
+        })
+        .linear_multiply(opacity);
+
+    let mut job = LayoutJob::single_section(
+        text.text.to_owned(),
+        TextFormat::simple(FontId::proportional(size), color),
+    );
+    (job.wrap.max_width, job.wrap.max_rows) = match (text.wrap, text.max_lines) {
+        (true, max_lines) => (wrap_width, max_lines),
+        (false, 0) => (f32::INFINITY, 0),
+        // A single line, cut off at the width
+        (false, _) => (wrap_width, 1),
+    };
+    job.halign = text.align.into();
+    job
+}
+
+impl EguiBackend<'_> {
+    fn galley(&self, text: &TextRun, wrap_width: f32) -> Arc<Galley> {
+        let job = layout_job(text, self.ui.style(), wrap_width, self.opacity);
+        self.ui.fonts(|fonts| fonts.layout_job(job))
+    }
+
+    fn selectable_text(&mut self, rect: egui::Rect, text: &TextRun) -> Response {
+        // The text edit paints the galley from its left edge. Alignment is done by the layout.
+        let mut job = layout_job(text, self.ui.style(), rect.width(), self.opacity);
+        job.halign = Align::LEFT;
+        let galley_width = self
+            .ui
+            .fonts(|fonts| fonts.layout_job(job.clone()))
+            .size()
+            .x;
+        let mut layouter = |ui: &Ui, _text: &str, _wrap_width: f32| -> Arc<Galley> {
+            ui.fonts(|fonts| fonts.layout_job(job.clone()))
+        };
+
+        // A read-only text edit gives selection and copy
+        let mut content = text.text;
+        let text_edit = TextEdit::multiline(&mut content)
+            .frame(false)
+            .margin(vec2(0., 0.))
+            .desired_rows(1)
+            .desired_width(galley_width)
+            .layouter(&mut layouter);
+
+        let layout = Layout::top_down(text.align.into());
+        self.ui
+            .allocate_ui_at_rect(rect, |ui| {
+                ui.with_layout(layout, |ui| ui.add(text_edit)).inner
+            })
+            .inner
+    }
+}
+
+impl Backend for EguiBackend<'_> {
+    fn available_rect(&self) -> Rect {
+        self.ui.available_rect_before_wrap().into()
+    }
+
+    fn item_spacing(&self) -> f32 {
+        self.ui.spacing().item_spacing.y
+    }
+
+    fn measure_text(&self, text: &TextRun, max: Size) -> Size {
+        self.galley(text, max.width).size().into()
+    }
+
+    fn measure_button(&self, label: &str, max: Size) -> Size {
+        // Same as egui::Button: the label with padding, at least the interact size
+        let style = self.ui.style();
+        let spacing = &style.spacing;
+        let padding = spacing.button_padding;
+        let mut text =
+            WidgetText::from(label).into_text_job(style, TextStyle::Button.into(), Align::Center);
+        text.job.wrap.max_width = max.width - 2. * padding.x;
+        let galley = self.ui.fonts(|fonts| text.into_galley(fonts));
+        let size = galley.size() + 2. * padding;
+        Size::new(
+            size.x.max(spacing.interact_size.x),
+            size.y.max(spacing.interact_size.y),
+        )
+    }
+
+    fn interact(&mut self, rect: Rect, sense: Sense) -> Interaction {
+        let response = self.ui.allocate_rect(rect.into(), sense.into());
+        let mut interaction = self.interaction(response);
+        if sense == Sense::Scroll && interaction.hovered {
+            // Taken, so what is around it does not scroll as well
+            let delta = self
+                .ui
+                .input_mut(|input| std::mem::take(&mut input.scroll_delta));
+            interaction.scroll = (delta.x, delta.y);
+        }
+        interaction
+    }
+
+    fn pointer(&self) -> Pointer {
+        self.ui.input(|input| {
+            let pointer = &input.pointer;
+            Pointer {
+                pos: pointer.interact_pos().map(|pos| (pos.x, pos.y)),
+                down: pointer.primary_down(),
+                pressed: pointer.primary_pressed(),
+                released: pointer.primary_released(),
+            }
+        })
+    }
+
+    fn gestures(&mut self, rect: Rect, kinds: &[GestureKind]) -> Vec<Gesture> {
+        // The same drawable gets the same id in every frame, like a widget
+        let id = self.ui.next_auto_id().with("gestures");
+        self.ui.skip_ahead_auto_ids(1);
+        let rect = rect.intersect(self.ui.clip_rect().into());
+        let over = self.ui.input(|input| {
+            let pos = input.pointer.hover_pos();
+            pos.map_or(false, |pos| rect.contains((pos.x, pos.y)))
+        });
+
+        let pinch = kinds.contains(&GestureKind::Pinch);
+        let input = self.ui.input_mut(|input| {
+            let touch = input.multi_touch();
+            let mut rotation = touch.map_or(0., |touch| touch.rotation_delta);
+            if pinch && over && input.modifiers.alt {
+                // Taken, so what is around it does not scroll as well
+                let delta = std::mem::take(&mut input.scroll_delta);
+                rotation += delta.y * RADIANS_PER_SCROLL;
+            }
+            GestureInput {
+                time: input.time,
+                pointer: Pointer::default(),
+                // Ctrl+scroll as well
+                zoom: input.zoom_delta(),
+                rotation,
+            }
+        });
+        let input = GestureInput {
+            pointer: self.pointer(),
+            ..input
+        };
+
+        let mut recognizer = self
+            .ui
+            .data_mut(|data| data.get_temp::<Recognizer>(id))
+            .unwrap_or_default();
+        let gestures = recognizer.update(rect, &input, kinds);
+        if let Some(due) = recognizer.long_press_due() {
+            // no input arrives while the pointer is held still
+            let remaining = Duration::from_secs_f64((due - input.time).max(0.));
+            self.ui.ctx().request_repaint_after(remaining);
+        }
+        self.ui.data_mut(|data| data.insert_temp(id, recognizer));
+        gestures
+    }
+
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        let rect = rect.into();
+        // Only when it is located in the visible part of the screen
+        if self.ui.is_rect_visible(rect) {
+            let color = self.color(color);
+            self.ui.painter().rect_filled(rect, Rounding::none(), color);
+        }
+    }
+
+    fn paint_path(&mut self, points: &[(f32, f32)], width: f32, color: Color) {
+        let points = points.iter().map(|&(x, y)| pos2(x, y)).collect();
+        let stroke = Stroke::new(width, self.color(color));
+        self.ui.painter().add(egui::Shape::line(points, stroke));
+    }
+
+    fn spinner(&mut self, rect: Rect, color: Color) {
+        let spinner = Spinner::new()
+            .size(rect.width.min(rect.height))
+            .color(self.color(color));
+        self.ui.put(rect.into(), spinner);
+    }
+
+    fn paint_box(&mut self, rect: Rect, style: &style::Style) {
+        let rect: egui::Rect = rect.into();
+        let rounding = Rounding::same(style.rounding);
+        let painter = self.ui.painter();
+        if let Some(shadow) = style.shadow {
+            let shadow_rect = rect.translate(shadow.offset.into());
+            let shape = epaint::Shadow {
+                extrusion: shadow.blur,
+                color: self.color(shadow.color),
+            }
+            .tessellate(shadow_rect, rounding);
+            painter.add(shape);
+        }
+        if let Some(background) = style.background {
+            painter.rect_filled(rect, rounding, self.color(background));
+        }
+        if let Some(border) = style.border {
+            let stroke = Stroke::new(border.width, self.color(border.color));
+            painter.rect_stroke(rect, rounding, stroke);
+        }
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    fn set_clip_rect(&mut self, rect: Rect) {
+        self.ui.set_clip_rect(rect.into());
+    }
+
+    fn clip_rect(&self) -> Rect {
+        self.ui.clip_rect().into()
+    }
+
+    fn image_status(&self, source: &ImageSource) -> ImageStatus {
+        assets::load(source)
+    }
+
+    fn paint_image(&mut self, rect: Rect, source: &ImageSource, uv: Rect, tint: Color) {
+        let rect: egui::Rect = rect.into();
+        if !self.ui.is_rect_visible(rect) {
+            return;
+        }
+        if let Some(texture) = self.texture(source) {
+            let uv = egui::Rect::from_min_size(pos2(uv.x, uv.y), vec2(uv.width, uv.height));
+            let tint = self.color(tint);
+            self.ui.painter().image(texture.id(), rect, uv, tint);
+        }
+    }
+
+    fn text(&mut self, rect: Rect, text: &TextRun) -> Interaction {
+        let rect: egui::Rect = rect.into();
+        if text.selectable {
+            let response = self.selectable_text(rect, text);
+            return self.interaction(response);
+        }
+
+        // Reserve space
+        let galley = self.galley(text, rect.width());
+        let response = self.ui.allocate_rect(rect, egui::Sense::click());
+
+        // Draw  - if it is located in the visible part of the screen
+        if self.ui.is_rect_visible(response.rect) {
+            // Rows are aligned around x = 0 of the galley
+            let align: Align = text.align.into();
+            let pos = [rect.min.x + align.to_factor() * rect.width(), rect.min.y];
+            let painter = self.ui.painter_at(rect);
+            if text.weight == FontWeight::Bold {
+                // The default fonts have no bold face. Draw twice, slightly offset.
+                painter.galley(pos.into(), galley.clone());
+                painter.galley([pos[0] + 0.5, pos[1]].into(), galley);
+            } else {
+                painter.galley(pos.into(), galley);
+            }
+        }
+        self.interaction(response)
+    }
+
+    fn measure_text_field(&self, field: &TextFieldRun, max: Size) -> Size {
+        // Same as egui::TextEdit with its default margin
+        let margin = vec2(4., 2.);
+        let row_height = self
+            .ui
+            .fonts(|fonts| fonts.row_height(&TextStyle::Body.resolve(self.ui.style())));
+        Size::new(
+            self.ui.spacing().text_edit_width.min(max.width),
+            field.rows as f32 * row_height + 2. * margin.y,
+        )
+    }
+
+    fn text_field(&mut self, rect: Rect, field: &TextFieldRun, text: &mut String) -> Interaction {
+        let mut text = CharLimit {
+            text,
+            max: field.max_length.unwrap_or(usize::MAX),
+        };
+        // The keyboard hint is for backends with a soft keyboard
+        let mut edit = if field.rows > 1 {
+            TextEdit::multiline(&mut text).desired_rows(field.rows)
+        } else {
+            TextEdit::singleline(&mut text)
+        }
+        .hint_text(field.placeholder)
+        .password(field.secure)
+        .text_color(self.color(field.color))
+        .desired_width(f32::INFINITY);
+        if let Some(id) = field.id {
+            edit = edit.id(Id::new(("rapp_text_field", id)));
+        }
+
+        let response = self
+            .ui
+            .allocate_ui_at_rect(rect.into(), |ui| {
+                ui.set_enabled(field.enabled);
+                ui.add_sized(ui.available_size(), edit)
+            })
+            .inner;
+        if field.request_focus {
+            response.request_focus();
+        } else if field.surrender_focus {
+            response.surrender_focus();
+        }
+
+        let submitted = response.lost_focus() && self.ui.input(|i| i.key_pressed(Key::Enter));
+        Interaction {
+            changed: response.changed(),
+            submitted: submitted && field.rows == 1,
+            focused: response.has_focus(),
+            ..self.interaction(response)
+        }
+    }
+
+    fn button(
+        &mut self,
+        rect: Rect,
+        label: &str,
+        colors: ButtonColors,
+        enabled: bool,
+    ) -> Interaction {
+        let text = RichText::new(label).color(self.color(colors.text));
+        let button = egui::Button::new(text).fill(self.color(colors.fill));
+        let response = self
+            .ui
+            .allocate_ui_at_rect(rect.into(), |ui| {
+                ui.set_enabled(enabled);
+                ui.centered_and_justified(|ui| ui.add(button)).inner
+            })
+            .inner;
+        self.interaction(response)
+    }
+}
+
+// Text that takes up to `max` characters, like `TextEdit::char_limit` of later egui versions.
+// What does not fit is not inserted, so the cursor stays within the text.
+struct CharLimit<'a> {
+    text: &'a mut String,
+    max: usize,
+}
+
+impl TextBuffer for CharLimit<'_> {
+    fn is_mutable(&self) -> bool {
+        true
+    }
+
+    fn as_str(&self) -> &str {
+        self.text
+    }
+
+    fn insert_text(&mut self, text: &str, char_index: usize) -> usize {
+        let room = self.max.saturating_sub(self.text.chars().count());
+        let text: String = text.chars().take(room).collect();
+        self.text.insert_text(&text, char_index)
+    }
+
+    fn delete_char_range(&mut self, char_range: Range<usize>) {
+        self.text.delete_char_range(char_range);
+    }
+}
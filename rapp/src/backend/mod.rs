@@ -0,0 +1,286 @@
+//! What drawables render against. A backend measures, paints and reports the input on the
+//! places the layout gave the drawables.
+
+#[cfg(feature = "egui")]
+pub mod egui;
+
+use crate::{
+    assets::{ImageSource, ImageStatus},
+    color::Color,
+    drawables::{
+        text::{FontWeight, TextAlign},
+        text_field::Keyboard,
+    },
+    gestures::{Gesture, GestureKind},
+    layout::{Rect, Size},
+    style::Style,
+    theme::Theme,
+};
+
+pub trait Backend {
+    /// The place drawables can use
+    fn available_rect(&self) -> Rect;
+
+    /// Space between the drawables a page queues
+    fn item_spacing(&self) -> f32;
+
+    /// Size of the text when it may use up to `max`
+    fn measure_text(&self, text: &TextRun, max: Size) -> Size;
+
+    /// Size of a button with this label when it may use up to `max`
+    fn measure_button(&self, label: &str, max: Size) -> Size;
+
+    /// Reacts to the pointer on `rect` without painting anything
+    fn interact(&mut self, rect: Rect, sense: Sense) -> Interaction;
+
+    /// Where the pointer is and what its button does, wherever that is
+    fn pointer(&self) -> Pointer;
+
+    /// The gestures of `kinds` on `rect` in this frame, also where other drawables are on top.
+    /// The backend keeps what the pointer did in earlier frames, see `Recognizer`.
+    fn gestures(&mut self, rect: Rect, kinds: &[GestureKind]) -> Vec<Gesture>;
+
+    /// Paints, without reacting to the pointer
+    fn fill_rect(&mut self, rect: Rect, color: Color);
+
+    /// Paints a line through the points
+    fn paint_path(&mut self, points: &[(f32, f32)], width: f32, color: Color);
+
+    /// A spinner that turns for as long as it is painted
+    fn spinner(&mut self, rect: Rect, color: Color);
+
+    /// Paints the shadow, background and border of the style around `rect`
+    fn paint_box(&mut self, rect: Rect, style: &Style);
+
+    /// Applies to everything painted from now on
+    fn set_opacity(&mut self, opacity: f32);
+
+    fn opacity(&self) -> f32;
+
+    /// Limits painting and input to `rect` from now on
+    fn set_clip_rect(&mut self, rect: Rect);
+
+    fn clip_rect(&self) -> Rect;
+
+    /// Where loading the image is at. The first call starts loading it.
+    fn image_status(&self, source: &ImageSource) -> ImageStatus;
+
+    /// Paints the part `uv` of a loaded image over `rect`, with its colors multiplied by `tint`.
+    /// `uv` is in texture coordinates, from 0 to 1.
+    fn paint_image(&mut self, rect: Rect, source: &ImageSource, uv: Rect, tint: Color);
+
+    fn text(&mut self, rect: Rect, text: &TextRun) -> Interaction;
+
+    fn measure_text_field(&self, field: &TextFieldRun, max: Size) -> Size;
+
+    /// An editable text. The edits of the user are made to `text`.
+    fn text_field(&mut self, rect: Rect, field: &TextFieldRun, text: &mut String) -> Interaction;
+
+    fn button(
+        &mut self,
+        rect: Rect,
+        label: &str,
+        colors: ButtonColors,
+        enabled: bool,
+    ) -> Interaction;
+}
+
+/// The colors of a button, from the theme
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ButtonColors {
+    pub fill: Color,
+    pub text: Color,
+}
+
+/// Text with the way it should look
+#[derive(Clone, Copy, Debug)]
+pub struct TextRun<'a> {
+    pub text: &'a str,
+    /// The default size of the backend when not set
+    pub size: Option<f32>,
+    pub weight: FontWeight,
+    /// A color of the backend that fits the weight when not set
+    pub color: Option<Color>,
+    pub align: TextAlign,
+    pub wrap: bool,
+    /// 0 means no limit. Without `wrap` any limit cuts off the line at the width.
+    pub max_lines: usize,
+    pub selectable: bool,
+}
+
+impl<'a> TextRun<'a> {
+    /// Body text of the theme on a single line, as for the label of a control
+    pub fn body(text: &'a str, theme: &Theme) -> Self {
+        let font = theme.typography.body;
+        Self {
+            text,
+            size: Some(font.size),
+            weight: font.weight,
+            color: Some(theme.text_color(font.weight)),
+            align: TextAlign::Left,
+            wrap: false,
+            max_lines: 1,
+            selectable: false,
+        }
+    }
+}
+
+/// How a text field looks and behaves, besides its text
+#[derive(Clone, Copy, Debug)]
+pub struct TextFieldRun<'a> {
+    pub placeholder: &'a str,
+    pub secure: bool,
+    /// For backends with a soft keyboard
+    pub keyboard: Keyboard,
+    /// In characters. Backends do not insert what does not fit.
+    pub max_length: Option<usize>,
+    /// 1 for a single line, which submits on enter
+    pub rows: usize,
+    pub enabled: bool,
+    pub color: Color,
+    /// Keeps the state of the field apart from others, like where the cursor is
+    pub id: Option<&'a str>,
+    pub request_focus: bool,
+    pub surrender_focus: bool,
+}
+
+/// What input a drawable listens to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sense {
+    Hover,
+    Click,
+    /// Clicks and drags, which report where the pointer is
+    Drag,
+    /// Hover, and takes the scrolling of a wheel or touchpad over it
+    Scroll,
+}
+
+/// What happened to a drawable in the frame it was drawn
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Interaction {
+    pub rect: Rect,
+    pub enabled: bool,
+    pub hovered: bool,
+    pub clicked: bool,
+    pub long_pressed: bool,
+    /// The text of a text field was edited
+    pub changed: bool,
+    pub submitted: bool,
+    /// It has the keyboard
+    pub focused: bool,
+    /// Where the pointer is while it presses or drags the drawable
+    pub pointer: Option<(f32, f32)>,
+    /// How far it was scrolled, for `Sense::Scroll`. Negative `y` moves the content up.
+    pub scroll: (f32, f32),
+}
+
+/// The pointer with its primary button, like a mouse or a finger
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Pointer {
+    /// None when there is no pointer, like between touches
+    pub pos: Option<(f32, f32)>,
+    pub down: bool,
+    /// Pressed in this frame
+    pub pressed: bool,
+    /// Released in this frame
+    pub released: bool,
+}
+
+impl Interaction {
+    /// Enabled, without any input
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            rect,
+            enabled: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// Measures with another backend, but does not paint and reports no input. It lets drawables
+/// be queued where there is nothing to draw on, like for semantics.
+pub(crate) struct MeasureOnly<'a>(pub &'a dyn Backend);
+
+impl Backend for MeasureOnly<'_> {
+    fn available_rect(&self) -> Rect {
+        self.0.available_rect()
+    }
+
+    fn item_spacing(&self) -> f32 {
+        self.0.item_spacing()
+    }
+
+    fn measure_text(&self, text: &TextRun, max: Size) -> Size {
+        self.0.measure_text(text, max)
+    }
+
+    fn measure_button(&self, label: &str, max: Size) -> Size {
+        self.0.measure_button(label, max)
+    }
+
+    fn interact(&mut self, rect: Rect, _sense: Sense) -> Interaction {
+        Interaction::new(rect)
+    }
+
+    fn pointer(&self) -> Pointer {
+        Pointer::default()
+    }
+
+    fn gestures(&mut self, _rect: Rect, _kinds: &[GestureKind]) -> Vec<Gesture> {
+        vec![]
+    }
+
+    fn fill_rect(&mut self, _rect: Rect, _color: Color) {}
+
+    fn paint_path(&mut self, _points: &[(f32, f32)], _width: f32, _color: Color) {}
+
+    fn spinner(&mut self, _rect: Rect, _color: Color) {}
+
+    fn paint_box(&mut self, _rect: Rect, _style: &Style) {}
+
+    fn set_opacity(&mut self, _opacity: f32) {}
+
+    fn opacity(&self) -> f32 {
+        self.0.opacity()
+    }
+
+    fn set_clip_rect(&mut self, _rect: Rect) {}
+
+    fn clip_rect(&self) -> Rect {
+        self.0.clip_rect()
+    }
+
+    fn image_status(&self, source: &ImageSource) -> ImageStatus {
+        self.0.image_status(source)
+    }
+
+    fn paint_image(&mut self, _rect: Rect, _source: &ImageSource, _uv: Rect, _tint: Color) {}
+
+    fn text(&mut self, rect: Rect, _text: &TextRun) -> Interaction {
+        Interaction::new(rect)
+    }
+
+    fn measure_text_field(&self, field: &TextFieldRun, max: Size) -> Size {
+        self.0.measure_text_field(field, max)
+    }
+
+    fn text_field(&mut self, rect: Rect, field: &TextFieldRun, _text: &mut String) -> Interaction {
+        Interaction {
+            enabled: field.enabled,
+            ..Interaction::new(rect)
+        }
+    }
+
+    fn button(
+        &mut self,
+        rect: Rect,
+        _label: &str,
+        _colors: ButtonColors,
+        enabled: bool,
+    ) -> Interaction {
+        Interaction {
+            enabled,
+            ..Interaction::new(rect)
+        }
+    }
+}
@@ -0,0 +1,45 @@
+/// An sRGB color with alpha
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const TRANSPARENT: Color = Color::rgba(0, 0, 0, 0);
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const GRAY: Color = Color::rgb(160, 160, 160);
+    pub const LIGHT_GRAY: Color = Color::rgb(220, 220, 220);
+    pub const DARK_GRAY: Color = Color::rgb(96, 96, 96);
+    pub const RED: Color = Color::rgb(255, 0, 0);
+    pub const GREEN: Color = Color::rgb(0, 255, 0);
+    pub const BLUE: Color = Color::rgb(0, 0, 255);
+    pub const GOLD: Color = Color::rgb(255, 215, 0);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba(r, g, b, 255)
+    }
+
+    /// Not premultiplied: `a` does not change the meaning of `r`, `g` and `b`
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+#[cfg(feature = "egui")]
+impl From<Color> for egui::Color32 {
+    fn from(color: Color) -> Self {
+        egui::Color32::from_rgba_unmultiplied(color.r, color.g, color.b, color.a)
+    }
+}
+
+#[cfg(feature = "egui")]
+impl From<egui::Color32> for Color {
+    fn from(color: egui::Color32) -> Self {
+        let [r, g, b, a] = color.to_srgba_unmultiplied();
+        Self::rgba(r, g, b, a)
+    }
+}
@@ -0,0 +1,200 @@
+use super::traits::{common_modifiers, Draw};
+use crate::{
+    backend::ButtonColors,
+    gestures::GestureHandlers,
+    layout::{LayoutCx, LayoutParams, Rect, Size},
+    message::Mailbox,
+    screen::{Screen, ScreenHandle},
+    semantics::{Role, SemanticNode},
+    style::Style,
+    theme::Theme,
+};
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+/// A button. Drawing happens after `Page::show` returns, so clicks are reported through
+/// callbacks. They run at the end of the frame in which the button was pressed.
+#[derive(Clone)]
+pub struct Button(pub(crate) Rc<RefCell<ButtonData>>);
+
+impl Button {
+    pub(crate) fn new(label: String, mailbox: Option<Rc<dyn Any>>) -> Self {
+        let data = ButtonData {
+            label,
+            mailbox,
+            ..Default::default()
+        };
+        Self(Rc::new(RefCell::new(data)))
+    }
+
+    /// A disabled button is greyed out and does not call its callbacks
+    pub fn enabled(self, enabled: bool) -> Self {
+        self.0.borrow_mut().enabled = enabled;
+        self
+    }
+    pub fn on_click(self, on_click: impl FnMut() + 'static) -> Self {
+        self.0.borrow_mut().on_click = Some(RefCell::new(Box::new(on_click)));
+        self
+    }
+    pub fn on_long_press(self, on_long_press: impl FnMut() + 'static) -> Self {
+        self.0.borrow_mut().on_long_press = Some(RefCell::new(Box::new(on_long_press)));
+        self
+    }
+    /// Posts `msg` to the `MessageApp` when clicked, instead of calling `on_click`
+    pub fn message<M: Clone + 'static>(self, msg: M) -> Self {
+        let mailbox = self.mailbox::<M>();
+        self.on_click(move || mailbox.post(msg.clone()))
+    }
+    /// Posts `msg` to the `MessageApp` when long pressed, instead of calling `on_long_press`
+    pub fn long_press_message<M: Clone + 'static>(self, msg: M) -> Self {
+        let mailbox = self.mailbox::<M>();
+        self.on_long_press(move || mailbox.post(msg.clone()))
+    }
+
+    fn mailbox<M: 'static>(&self) -> Mailbox<M> {
+        Mailbox::downcast(self.0.borrow().mailbox.as_ref())
+    }
+}
+
+common_modifiers!(Button);
+
+type Callback = RefCell<Box<dyn FnMut()>>;
+
+pub(crate) struct ButtonData {
+    pub label: String,
+    pub enabled: bool,
+    pub on_click: Option<Callback>,
+    pub on_long_press: Option<Callback>,
+    pub layout: LayoutParams,
+    pub test_id: Option<String>,
+    pub gestures: GestureHandlers,
+    pub style: Style,
+    // The Mailbox<M> of the screen that made it
+    pub mailbox: Option<Rc<dyn Any>>,
+    pub theme: Rc<Theme>,
+}
+
+impl Default for ButtonData {
+    fn default() -> Self {
+        Self {
+            label: String::new(),
+            enabled: true,
+            on_click: None,
+            on_long_press: None,
+            layout: LayoutParams::default(),
+            test_id: None,
+            gestures: GestureHandlers::default(),
+            style: Style::default(),
+            mailbox: None,
+            theme: Rc::default(),
+        }
+    }
+}
+
+impl Draw for ButtonData {
+    fn params(&self) -> LayoutParams {
+        self.layout
+    }
+
+    fn style(&self) -> Style {
+        self.style
+    }
+
+    fn gestures(&self) -> Option<&GestureHandlers> {
+        Some(&self.gestures)
+    }
+
+    fn measure(&self, cx: &LayoutCx, max: Size) -> Size {
+        cx.backend.measure_button(&self.label, max)
+    }
+
+    fn draw(&self, screen: &mut Screen, rect: Rect) -> ScreenHandle {
+        let colors = ButtonColors {
+            fill: self.theme.colors.primary,
+            text: self.theme.colors.on_primary,
+        };
+        let interaction = screen
+            .backend()
+            .button(rect, &self.label, colors, self.enabled);
+
+        ScreenHandle::new(interaction)
+    }
+
+    fn semantics(&self, _cx: &LayoutCx, rect: Rect) -> SemanticNode {
+        SemanticNode {
+            label: Some(self.label.clone()),
+            enabled: self.enabled,
+            test_id: self.test_id.clone(),
+            ..SemanticNode::new(Role::Button, rect)
+        }
+    }
+
+    fn respond(&self, handle: &ScreenHandle) {
+        if !handle.enabled() {
+            return;
+        }
+        if handle.clicked() {
+            if let Some(on_click) = &self.on_click {
+                (on_click.borrow_mut())();
+            }
+        }
+        if handle.long_pressed() {
+            if let Some(on_long_press) = &self.on_long_press {
+                (on_long_press.borrow_mut())();
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "egui"))]
+mod tests {
+    use crate::layout::tests::egui_frame;
+    use egui::{pos2, Context, Event, PointerButton};
+    use std::{cell::Cell, rc::Rc};
+
+    // Runs one frame with a single button at the top left, pressing or releasing on it
+    fn frame(
+        ctx: &Context,
+        time: f64,
+        pressed: Option<bool>,
+        clicks: &Rc<Cell<u8>>,
+        longs: &Rc<Cell<u8>>,
+    ) {
+        let pos = pos2(20., 18.);
+        let mut events = vec![Event::PointerMoved(pos)];
+        if let Some(pressed) = pressed {
+            events.push(Event::PointerButton {
+                pos,
+                button: PointerButton::Primary,
+                pressed,
+                modifiers: Default::default(),
+            });
+        }
+        egui_frame(ctx, Some(time), events, |screen| {
+            let (clicks, longs) = (clicks.clone(), longs.clone());
+            screen
+                .button("Press me")
+                .on_click(move || clicks.set(clicks.get() + 1))
+                .on_long_press(move || longs.set(longs.get() + 1));
+        });
+    }
+
+    #[test]
+    fn click_and_long_press() {
+        let ctx = Context::default();
+        let clicks = Rc::new(Cell::new(0));
+        let longs = Rc::new(Cell::new(0));
+
+        frame(&ctx, 0.0, None, &clicks, &longs);
+        frame(&ctx, 0.1, Some(true), &clicks, &longs);
+        frame(&ctx, 0.2, Some(false), &clicks, &longs);
+        assert_eq!((clicks.get(), longs.get()), (1, 0));
+
+        // Held past the threshold: one long press and no click on release
+        frame(&ctx, 1.0, Some(true), &clicks, &longs);
+        frame(&ctx, 1.3, None, &clicks, &longs);
+        frame(&ctx, 1.6, None, &clicks, &longs);
+        frame(&ctx, 1.7, None, &clicks, &longs);
+        frame(&ctx, 1.8, Some(false), &clicks, &longs);
+        assert_eq!((clicks.get(), longs.get()), (1, 1));
+    }
+}
@@ -0,0 +1,211 @@
+use super::traits::{common_modifiers, Draw};
+use crate::{
+    assets::{ImageSource, ImageStatus},
+    backend::Sense,
+    color::Color,
+    gestures::GestureHandlers,
+    layout::{LayoutCx, LayoutParams, Rect, Size},
+    screen::{Screen, ScreenHandle},
+    semantics::{Role, SemanticNode},
+    style::Style,
+    theme::Theme,
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// How an image fills the place the layout gives it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImageFit {
+    /// All of the image shows, as large as fits. The rest of the place stays empty.
+    #[default]
+    Contain,
+    /// The image fills the place. What sticks out is cut off.
+    Cover,
+    /// The image is stretched to fill the place
+    Fill,
+}
+
+impl ImageFit {
+    /// Where an image of `size` goes within `rect`, and the part of it that shows there. The
+    /// part is in texture coordinates, from 0 to 1.
+    pub fn place(self, size: Size, rect: Rect) -> (Rect, Rect) {
+        let whole = Rect::new(0., 0., 1., 1.);
+        if size.width <= 0. || size.height <= 0. {
+            return (rect, whole);
+        }
+        let scale_x = rect.width / size.width;
+        let scale_y = rect.height / size.height;
+        match self {
+            ImageFit::Contain => {
+                let scale = scale_x.min(scale_y);
+                let (width, height) = (size.width * scale, size.height * scale);
+                let x = rect.x + (rect.width - width) / 2.;
+                let y = rect.y + (rect.height - height) / 2.;
+                (Rect::new(x, y, width, height), whole)
+            }
+            ImageFit::Cover => {
+                let scale = scale_x.max(scale_y);
+                let width = rect.width / (size.width * scale);
+                let height = rect.height / (size.height * scale);
+                let uv = Rect::new((1. - width) / 2., (1. - height) / 2., width, height);
+                (rect, uv)
+            }
+            ImageFit::Fill => (rect, whole),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Image(pub(crate) Rc<RefCell<ImageData>>);
+
+impl Image {
+    pub(crate) fn new(source: ImageSource) -> Self {
+        Self(Rc::new(RefCell::new(ImageData::new(source))))
+    }
+
+    pub fn fit(self, fit: ImageFit) -> Self {
+        self.0.borrow_mut().fit = fit;
+        self
+    }
+    /// Multiplies the colors of the image, like white with an alpha to fade it
+    pub fn tint(self, color: Color) -> Self {
+        self.0.borrow_mut().tint = Some(color);
+        self
+    }
+    /// Fills the place while the image loads or when it failed to load. The surface color of
+    /// the theme, unless set.
+    pub fn placeholder(self, color: Color) -> Self {
+        self.0.borrow_mut().placeholder = Some(color);
+        self
+    }
+    /// What the image shows, in words
+    pub fn description(self, description: impl Into<String>) -> Self {
+        self.0.borrow_mut().description = Some(description.into());
+        self
+    }
+}
+
+common_modifiers!(Image);
+
+#[derive(Clone)]
+pub(crate) struct ImageData {
+    pub source: ImageSource,
+    pub fit: ImageFit,
+    pub tint: Option<Color>,
+    pub placeholder: Option<Color>,
+    pub description: Option<String>,
+    pub layout: LayoutParams,
+    pub test_id: Option<String>,
+    pub gestures: GestureHandlers,
+    pub style: Style,
+    pub theme: Rc<Theme>,
+}
+
+impl ImageData {
+    fn new(source: ImageSource) -> Self {
+        Self {
+            source,
+            fit: ImageFit::Contain,
+            tint: None,
+            placeholder: None,
+            description: None,
+            layout: LayoutParams::default(),
+            test_id: None,
+            gestures: GestureHandlers::default(),
+            style: Style::default(),
+            theme: Rc::default(),
+        }
+    }
+}
+
+impl Draw for ImageData {
+    fn params(&self) -> LayoutParams {
+        self.layout
+    }
+
+    fn style(&self) -> Style {
+        self.style
+    }
+
+    fn gestures(&self) -> Option<&GestureHandlers> {
+        Some(&self.gestures)
+    }
+
+    /// The size of the image, smaller when it does not fit. Nothing while it loads, so give it
+    /// a size to keep its place.
+    fn measure(&self, cx: &LayoutCx, max: Size) -> Size {
+        match cx.backend.image_status(&self.source) {
+            ImageStatus::Ready(size) => {
+                let scale = (max.width / size.width)
+                    .min(max.height / size.height)
+                    .min(1.);
+                Size::new(size.width * scale, size.height * scale)
+            }
+            ImageStatus::Loading | ImageStatus::Failed(_) => Size::ZERO,
+        }
+    }
+
+    fn draw(&self, screen: &mut Screen, rect: Rect) -> ScreenHandle {
+        let backend = screen.backend();
+        let interaction = backend.interact(rect, Sense::Click);
+
+        match backend.image_status(&self.source) {
+            ImageStatus::Ready(size) => {
+                let (image_rect, uv) = self.fit.place(size, rect);
+                let tint = self.tint.unwrap_or(Color::WHITE);
+                backend.paint_image(image_rect, &self.source, uv, tint);
+            }
+            ImageStatus::Loading | ImageStatus::Failed(_) => {
+                let color = self.placeholder.unwrap_or(self.theme.colors.surface);
+                backend.fill_rect(rect, color);
+            }
+        }
+        ScreenHandle::new(interaction)
+    }
+
+    fn semantics(&self, _cx: &LayoutCx, rect: Rect) -> SemanticNode {
+        SemanticNode {
+            label: self.description.clone(),
+            test_id: self.test_id.clone(),
+            ..SemanticNode::new(Role::Image, rect)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ImageFit;
+    use crate::{
+        drawables::traits::{Sizing, TestId},
+        layout::{
+            tests::{TestBackend, TestRoute},
+            Rect, Size,
+        },
+        navigation::Navigator,
+        screen::Screen,
+    };
+
+    #[test]
+    fn fit_modes() {
+        let size = Size::new(100., 50.);
+        let rect = Rect::new(10., 10., 50., 50.);
+        let whole = Rect::new(0., 0., 1., 1.);
+
+        let (contain, uv) = ImageFit::Contain.place(size, rect);
+        assert_eq!((contain, uv), (Rect::new(10., 22.5, 50., 25.), whole));
+        // Half of the width shows, from the middle
+        let (cover, uv) = ImageFit::Cover.place(size, rect);
+        assert_eq!((cover, uv), (rect, Rect::new(0.25, 0., 0.5, 1.)));
+        assert_eq!(ImageFit::Fill.place(size, rect), (rect, whole));
+
+        // The test backend has every image at 100 by 50
+        let mut backend = TestBackend;
+        let mut screen = Screen::new(&mut backend, Navigator::new(TestRoute));
+        screen.image("wide.png").test_id("wide");
+        screen.image("narrow.png").test_id("narrow").width(40);
+        let tree = screen.semantics();
+        let wide = tree.find_by_test_id("wide").unwrap();
+        assert_eq!(wide.bounds.size(), size);
+        let narrow = tree.find_by_test_id("narrow").unwrap();
+        assert_eq!(narrow.bounds.size(), Size::new(40., 20.));
+    }
+}
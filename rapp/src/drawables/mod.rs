@@ -1,3 +1,10 @@
+pub mod button;
+pub mod image;
 pub mod placeholder;
+pub mod radio;
 pub mod separator;
+pub mod slider;
+pub mod text;
+pub mod text_field;
+pub mod toggle;
 pub mod traits;
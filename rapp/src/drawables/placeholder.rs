@@ -1,74 +1,78 @@
-use super::traits::{Draw, EguiDraw};
-use crate::screen::{Screen, ScreenHandle};
-use egui::{vec2, Color32, Rect, Response, Rounding, Sense, Ui};
+use super::traits::{common_modifiers, Draw};
+use crate::{
+    backend::Sense,
+    gestures::GestureHandlers,
+    layout::{LayoutCx, LayoutParams, Length, Rect, Size},
+    screen::{Screen, ScreenHandle},
+    semantics::{Role, SemanticNode},
+    style::Style,
+    theme::Theme,
+};
 use std::{cell::RefCell, rc::Rc};
 
 #[derive(Clone, Default)]
 pub struct PlaceHolder(pub(crate) Rc<RefCell<PlaceHolderData>>);
 
-impl PlaceHolder {
-    pub fn width(self, width: u16) -> Self {
-        self.0.borrow_mut().width = width.into();
-        self
-    }
-    pub fn height(self, heigth: u16) -> Self {
-        self.0.borrow_mut().heigth = heigth.into();
-        self
-    }
-}
+common_modifiers!(PlaceHolder);
 
 #[derive(Clone)]
 pub struct PlaceHolderData {
-    pub width: f32,
-    pub heigth: f32,
+    pub layout: LayoutParams,
+    pub test_id: Option<String>,
+    pub gestures: GestureHandlers,
+    pub style: Style,
+    pub theme: Rc<Theme>,
 }
 
 impl Default for PlaceHolderData {
     fn default() -> Self {
         Self {
-            width: 100.,
-            heigth: 100.,
+            layout: LayoutParams::fixed(100., 100.),
+            test_id: None,
+            gestures: GestureHandlers::default(),
+            style: Style::default(),
+            theme: Rc::default(),
         }
     }
 }
 
 impl Draw for PlaceHolderData {
-    fn draw(&self, screen: &mut Screen) -> ScreenHandle {
-        let response = self.draw_egui(screen.egui_ui);
+    fn params(&self) -> LayoutParams {
+        self.layout
+    }
 
-        ScreenHandle {
-            _egui_response: response,
-        }
+    fn style(&self) -> Style {
+        self.style
     }
-}
 
-impl EguiDraw for PlaceHolderData {
-    fn draw_egui(&self, ui: &mut Ui) -> Response {
-        // Reserve space
-        let available_space = ui.available_size_before_wrap();
-        let height = if self.heigth == f32::INFINITY {
-            available_space.y
-        } else {
-            self.heigth
-        };
-        let width = if self.width == f32::INFINITY {
-            available_space.x
-        } else {
-            self.width
+    fn gestures(&self) -> Option<&GestureHandlers> {
+        Some(&self.gestures)
+    }
+
+    fn measure(&self, _cx: &LayoutCx, _max: Size) -> Size {
+        Size::ZERO
+    }
+
+    fn draw(&self, screen: &mut Screen, rect: Rect) -> ScreenHandle {
+        let backend = screen.backend();
+        let interaction = backend.interact(rect, Sense::Click);
+
+        // The requested width, which the layout may have stretched or shrunk
+        let width = match self.layout.width {
+            Length::Fixed(width) => width.min(rect.width),
+            Length::WrapContent | Length::MatchParent => rect.width,
         };
-        let nest_size = vec2(width, height);
-        let (reserved_rect, response) = Ui::allocate_at_least(ui, nest_size, Sense::click());
+        let colors = &self.theme.colors;
+        backend.fill_rect(rect, colors.accent);
+        backend.fill_rect(Rect { width, ..rect }, colors.primary);
+
+        ScreenHandle::new(interaction)
+    }
 
-        // Draw  - if it is located in the visible part of the screen
-        if ui.is_rect_visible(response.rect) {
-            let painter = ui.painter();
-            let draw_rect = Rect {
-                min: reserved_rect.min,
-                max: [reserved_rect.min.x + width, reserved_rect.max.y].into(),
-            };
-            painter.rect_filled(reserved_rect, Rounding::none(), Color32::GOLD);
-            painter.rect_filled(draw_rect, Rounding::none(), Color32::GREEN);
+    fn semantics(&self, _cx: &LayoutCx, rect: Rect) -> SemanticNode {
+        SemanticNode {
+            test_id: self.test_id.clone(),
+            ..SemanticNode::new(Role::Placeholder, rect)
         }
-        response
     }
 }
@@ -0,0 +1,252 @@
+use super::{
+    toggle::{draw_labeled, measure_labeled},
+    traits::{common_modifiers, Draw},
+};
+use crate::{
+    backend::{Backend, Interaction},
+    gestures::GestureHandlers,
+    layout::{LayoutCx, LayoutParams, Rect, Size},
+    screen::{Screen, ScreenHandle},
+    semantics::{Role, SemanticNode},
+    store::Store,
+    style::{Border, Style},
+    theme::Theme,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+const RADIO: Size = Size::new(20., 20.);
+
+/// Options below each other, of which one is selected. The selected value is in a store,
+/// typically of an enum. A click selects an option at the end of the frame, before the
+/// callbacks run.
+pub struct RadioGroup<T>(pub(crate) Rc<RefCell<RadioGroupData<T>>>);
+
+impl<T: Clone + PartialEq + 'static> RadioGroup<T> {
+    pub(crate) fn new(value: Store<T>, options: Vec<(T, String)>) -> Self {
+        Self(Rc::new(RefCell::new(RadioGroupData {
+            value,
+            options,
+            enabled: true,
+            on_change: None,
+            layout: LayoutParams::default(),
+            test_id: None,
+            gestures: GestureHandlers::default(),
+            style: Style::default(),
+            theme: Rc::default(),
+            clicked: Cell::new(None),
+        })))
+    }
+
+    /// A disabled group is faded and does not change
+    pub fn enabled(self, enabled: bool) -> Self {
+        self.0.borrow_mut().enabled = enabled;
+        self
+    }
+    /// Called with the newly selected value
+    pub fn on_change(self, on_change: impl FnMut(&T) + 'static) -> Self {
+        self.0.borrow_mut().on_change = Some(RefCell::new(Box::new(on_change)));
+        self
+    }
+}
+
+impl<T> Clone for RadioGroup<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+common_modifiers!(impl<T> RadioGroup<T>);
+
+pub(crate) struct RadioGroupData<T> {
+    pub value: Store<T>,
+    pub options: Vec<(T, String)>,
+    pub enabled: bool,
+    pub on_change: Option<Callback<T>>,
+    pub layout: LayoutParams,
+    pub test_id: Option<String>,
+    pub gestures: GestureHandlers,
+    pub style: Style,
+    pub theme: Rc<Theme>,
+    // The option that was clicked while drawing
+    clicked: Cell<Option<usize>>,
+}
+
+type Callback<T> = RefCell<Box<dyn FnMut(&T)>>;
+
+impl<T: PartialEq> RadioGroupData<T> {
+    // Where the options go within `rect`
+    fn rows(&self, backend: &dyn Backend, rect: Rect) -> Vec<Rect> {
+        let spacing = f32::from(self.theme.spacing.s);
+        let mut y = rect.y;
+        self.options
+            .iter()
+            .map(|(_, label)| {
+                let size = measure_labeled(backend, &self.theme, RADIO, label, rect.size());
+                let row = Rect::new(rect.x, y, rect.width, size.height);
+                y += size.height + spacing;
+                row
+            })
+            .collect()
+    }
+
+    fn selected(&self, index: usize) -> bool {
+        *self.value.read() == self.options[index].0
+    }
+
+    fn paint_radio(&self, backend: &mut dyn Backend, rect: Rect, selected: bool) {
+        let colors = &self.theme.colors;
+        let ring = Style {
+            border: Some(Border {
+                width: 2.,
+                color: if selected {
+                    colors.primary
+                } else {
+                    colors.separator
+                },
+            }),
+            rounding: rect.height / 2.,
+            ..Style::default()
+        };
+        backend.paint_box(rect, &ring);
+        if selected {
+            let dot = rect.shrink(5.);
+            let dot_style = Style {
+                background: Some(colors.primary),
+                rounding: dot.height / 2.,
+                ..Style::default()
+            };
+            backend.paint_box(dot, &dot_style);
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> Draw for RadioGroupData<T> {
+    fn params(&self) -> LayoutParams {
+        self.layout
+    }
+
+    fn style(&self) -> Style {
+        self.style
+    }
+
+    fn gestures(&self) -> Option<&GestureHandlers> {
+        Some(&self.gestures)
+    }
+
+    fn measure(&self, cx: &LayoutCx, max: Size) -> Size {
+        let spacing = f32::from(self.theme.spacing.s);
+        let size = self.options.iter().fold(Size::ZERO, |size, (_, label)| {
+            let row = measure_labeled(cx.backend, &self.theme, RADIO, label, max);
+            Size::new(
+                size.width.max(row.width),
+                size.height + row.height + spacing,
+            )
+        });
+        Size::new(size.width, (size.height - spacing).max(0.))
+    }
+
+    fn draw(&self, screen: &mut Screen, rect: Rect) -> ScreenHandle {
+        let backend = screen.backend();
+        for (index, row) in self.rows(backend, rect).into_iter().enumerate() {
+            let selected = self.selected(index);
+            let label = &self.options[index].1;
+            let interaction = draw_labeled(
+                backend,
+                &self.theme,
+                row,
+                RADIO,
+                label,
+                self.enabled,
+                |backend, rect| self.paint_radio(backend, rect, selected),
+            );
+            if interaction.clicked && interaction.enabled {
+                self.clicked.set(Some(index));
+            }
+        }
+        ScreenHandle::new(Interaction {
+            enabled: self.enabled,
+            ..Interaction::new(rect)
+        })
+    }
+
+    fn semantics(&self, cx: &LayoutCx, rect: Rect) -> SemanticNode {
+        let children = self
+            .rows(cx.backend, rect)
+            .into_iter()
+            .enumerate()
+            .map(|(index, row)| SemanticNode {
+                label: Some(self.options[index].1.clone()),
+                checked: Some(self.selected(index)),
+                enabled: self.enabled,
+                ..SemanticNode::new(Role::RadioButton, row)
+            })
+            .collect();
+        SemanticNode {
+            enabled: self.enabled,
+            test_id: self.test_id.clone(),
+            children,
+            ..SemanticNode::new(Role::RadioGroup, rect)
+        }
+    }
+
+    fn respond(&self, _handle: &ScreenHandle) {
+        let Some(index) = self.clicked.take() else {
+            return;
+        };
+        if self.selected(index) {
+            return;
+        }
+        let value = self.options[index].0.clone();
+        self.value.set(value.clone());
+        if let Some(on_change) = &self.on_change {
+            (on_change.borrow_mut())(&value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        drawables::traits::TestId,
+        layout::{
+            tests::{TestBackend, TestRoute},
+            Rect,
+        },
+        navigation::Navigator,
+        screen::Screen,
+        semantics::Role,
+        store::Store,
+    };
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Size {
+        Small,
+        Large,
+    }
+
+    #[test]
+    fn options_below_each_other() {
+        let size = Store::new(Size::Large);
+        let mut backend = TestBackend;
+        let mut screen = Screen::new(&mut backend, Navigator::new(TestRoute));
+        screen
+            .radio_group(&size, [(Size::Small, "Small"), (Size::Large, "Large")])
+            .test_id("size");
+
+        let tree = screen.semantics();
+        let group = tree.find_by_test_id("size").unwrap();
+        assert_eq!(group.role, Role::RadioGroup);
+        // The radio, a gap of 8 and the label, with 4 between the rows
+        assert_eq!(
+            group.bounds,
+            Rect::new(0., 0., 20. + 8. + 40., 20. + 4. + 20.)
+        );
+        let large = tree.find_by_text("Large").unwrap();
+        assert_eq!(large.bounds, Rect::new(0., 24., 68., 20.));
+        assert_eq!(large.checked, Some(true));
+        assert_eq!(tree.find_by_text("Small").unwrap().checked, Some(false));
+    }
+}
@@ -1,6 +1,13 @@
-use super::traits::{Draw, EguiDraw};
-use crate::screen::{Screen, ScreenHandle};
-use egui::{vec2, Color32, Rect, Response, Rounding, Sense, Ui};
+use super::traits::{common_modifiers, Draw};
+use crate::{
+    backend::Sense,
+    gestures::GestureHandlers,
+    layout::{LayoutCx, LayoutParams, Length, Rect, Size},
+    screen::{Screen, ScreenHandle},
+    semantics::{Role, SemanticNode},
+    style::Style,
+    theme::Theme,
+};
 use std::{cell::RefCell, rc::Rc};
 
 #[derive(Clone, Default)]
@@ -11,53 +18,83 @@ impl Separator {
         self.0.borrow_mut().thickness = thickness.into();
         self
     }
+    /// Empty space around the line, half above and half below it
+    pub fn space(self, space: u16) -> Self {
+        self.0.borrow_mut().space = space.into();
+        self
+    }
 }
 
+common_modifiers!(Separator);
+
 #[derive(Clone)]
 pub(crate) struct SeparatorData {
     pub thickness: f32,
+    pub space: f32,
+    pub layout: LayoutParams,
+    pub test_id: Option<String>,
+    pub gestures: GestureHandlers,
+    pub style: Style,
+    pub theme: Rc<Theme>,
 }
 
 impl Default for SeparatorData {
     fn default() -> Self {
-        Self { thickness: 2. }
+        Self {
+            thickness: 2.,
+            space: 10.,
+            layout: LayoutParams {
+                width: Length::MatchParent,
+                ..Default::default()
+            },
+            test_id: None,
+            gestures: GestureHandlers::default(),
+            style: Style::default(),
+            theme: Rc::default(),
+        }
     }
 }
 
 impl Draw for SeparatorData {
-    fn draw(&self, screen: &mut Screen) -> ScreenHandle {
-        let response = self.draw_egui(screen.egui_ui);
+    fn params(&self) -> LayoutParams {
+        self.layout
+    }
 
-        ScreenHandle {
-            _egui_response: response,
-        }
+    fn style(&self) -> Style {
+        self.style
+    }
+
+    fn gestures(&self) -> Option<&GestureHandlers> {
+        Some(&self.gestures)
+    }
+
+    fn measure(&self, _cx: &LayoutCx, _max: Size) -> Size {
+        Size::new(0., self.thickness + self.space)
+    }
+
+    fn draw(&self, screen: &mut Screen, rect: Rect) -> ScreenHandle {
+        let backend = screen.backend();
+        let interaction = backend.interact(rect, Sense::Click);
+
+        // The line in the middle of the space
+        let thickness = self.thickness.min(rect.height);
+        let y = rect.y + (rect.height - thickness) / 2.;
+        backend.fill_rect(
+            Rect {
+                y,
+                height: thickness,
+                ..rect
+            },
+            self.theme.colors.separator,
+        );
+
+        ScreenHandle::new(interaction)
     }
-}
 
-impl EguiDraw for SeparatorData {
-    fn draw_egui(&self, ui: &mut Ui) -> Response {
-        // Reserve space
-        let available_space = ui.available_size_before_wrap();
-        let height = self.thickness + 10.0;
-        let width = available_space.x;
-        let requested_size = vec2(width, height);
-
-        let (given_rect, response) = Ui::allocate_at_least(ui, requested_size, Sense::click());
-
-        // Draw  - if it is located in the visible part of the screen
-        if ui.is_rect_visible(response.rect) {
-            let painter = ui.painter();
-            let draw_rect = Rect {
-                min: [given_rect.min.x, given_rect.min.y + 10. / 2.].into(),
-                max: [given_rect.max.x, given_rect.max.y - 10. / 2.].into(),
-            };
-            // painter.rect_filled(
-            //     given_rect,
-            //     Rounding::none(),
-            //     Color32::GOLD.gamma_multiply(0.1),
-            // );
-            painter.rect_filled(draw_rect, Rounding::none(), Color32::GRAY);
+    fn semantics(&self, _cx: &LayoutCx, rect: Rect) -> SemanticNode {
+        SemanticNode {
+            test_id: self.test_id.clone(),
+            ..SemanticNode::new(Role::Separator, rect)
         }
-        response
     }
 }
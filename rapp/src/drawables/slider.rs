@@ -0,0 +1,254 @@
+use super::{
+    toggle::DISABLED_OPACITY,
+    traits::{common_modifiers, Draw},
+};
+use crate::{
+    backend::{Interaction, Sense},
+    gestures::GestureHandlers,
+    layout::{LayoutCx, LayoutParams, Rect, Size},
+    screen::{Screen, ScreenHandle},
+    semantics::{Role, SemanticNode},
+    store::Store,
+    style::Style,
+    theme::Theme,
+};
+use std::{
+    cell::{Cell, RefCell},
+    ops::RangeInclusive,
+    rc::Rc,
+};
+
+const HEIGHT: f32 = 20.;
+const TRACK: f32 = 4.;
+const KNOB: f32 = 16.;
+/// When there is no width to fill
+const DEFAULT_WIDTH: f32 = 200.;
+
+/// Picks a number from a range by dragging, bound to a store. The value is written at the end
+/// of every frame in which it is dragged, before the callbacks run.
+#[derive(Clone)]
+pub struct Slider(pub(crate) Rc<RefCell<SliderData>>);
+
+impl Slider {
+    pub(crate) fn new(value: Store<f32>, range: RangeInclusive<f32>) -> Self {
+        Self(Rc::new(RefCell::new(SliderData {
+            value,
+            range,
+            step: None,
+            enabled: true,
+            on_change: None,
+            layout: LayoutParams::default(),
+            test_id: None,
+            gestures: GestureHandlers::default(),
+            style: Style::default(),
+            theme: Rc::default(),
+            dragged: Cell::new(None),
+        })))
+    }
+
+    /// Values are a whole number of steps from the start of the range
+    pub fn step(self, step: f32) -> Self {
+        self.0.borrow_mut().step = Some(step).filter(|step| *step > 0.);
+        self
+    }
+    /// A disabled slider is faded and does not change
+    pub fn enabled(self, enabled: bool) -> Self {
+        self.0.borrow_mut().enabled = enabled;
+        self
+    }
+    /// Called with the new value
+    pub fn on_change(self, on_change: impl FnMut(f32) + 'static) -> Self {
+        self.0.borrow_mut().on_change = Some(RefCell::new(Box::new(on_change)));
+        self
+    }
+}
+
+common_modifiers!(Slider);
+
+pub(crate) struct SliderData {
+    pub value: Store<f32>,
+    pub range: RangeInclusive<f32>,
+    pub step: Option<f32>,
+    pub enabled: bool,
+    pub on_change: Option<Callback>,
+    pub layout: LayoutParams,
+    pub test_id: Option<String>,
+    pub gestures: GestureHandlers,
+    pub style: Style,
+    pub theme: Rc<Theme>,
+    // The value under the pointer while drawing
+    dragged: Cell<Option<f32>>,
+}
+
+type Callback = RefCell<Box<dyn FnMut(f32)>>;
+
+impl SliderData {
+    // The line the knob moves along. The knob stays within `rect`.
+    fn track(rect: Rect) -> Rect {
+        let y = rect.y + (rect.height - TRACK) / 2.;
+        Rect::new(rect.x + KNOB / 2., y, (rect.width - KNOB).max(0.), TRACK)
+    }
+
+    /// The value at `fraction` of the range, on a step
+    fn value_at(&self, fraction: f32) -> f32 {
+        let (start, end) = (*self.range.start(), *self.range.end());
+        let mut value = start + fraction.clamp(0., 1.) * (end - start);
+        if let Some(step) = self.step {
+            value = start + ((value - start) / step).round() * step;
+        }
+        value.clamp(start.min(end), start.max(end))
+    }
+
+    fn fraction(&self, value: f32) -> f32 {
+        let (start, end) = (*self.range.start(), *self.range.end());
+        if start == end {
+            return 0.;
+        }
+        ((value - start) / (end - start)).clamp(0., 1.)
+    }
+}
+
+impl Draw for SliderData {
+    fn params(&self) -> LayoutParams {
+        self.layout
+    }
+
+    fn style(&self) -> Style {
+        self.style
+    }
+
+    fn gestures(&self) -> Option<&GestureHandlers> {
+        Some(&self.gestures)
+    }
+
+    fn measure(&self, _cx: &LayoutCx, max: Size) -> Size {
+        let width = if max.width.is_finite() {
+            max.width
+        } else {
+            DEFAULT_WIDTH
+        };
+        Size::new(width, HEIGHT)
+    }
+
+    fn draw(&self, screen: &mut Screen, rect: Rect) -> ScreenHandle {
+        let backend = screen.backend();
+        let interaction = backend.interact(rect, Sense::Drag);
+        let track = Self::track(rect);
+
+        let mut value = *self.value.read();
+        // Narrower than the knob there is no track to drag along, and the value stays
+        let draggable = self.enabled && interaction.enabled && track.width > 0.;
+        if let (true, Some((x, _))) = (draggable, interaction.pointer) {
+            value = self.value_at((x - track.x) / track.width);
+            self.dragged.set(Some(value));
+        }
+
+        let opacity = backend.opacity();
+        if !self.enabled {
+            backend.set_opacity(opacity * DISABLED_OPACITY);
+        }
+        let colors = &self.theme.colors;
+        let round = |color, height: f32| Style {
+            background: Some(color),
+            rounding: height / 2.,
+            ..Style::default()
+        };
+        backend.paint_box(track, &round(colors.separator, TRACK));
+        let knob_x = track.x + self.fraction(value) * track.width;
+        let filled = Rect {
+            width: knob_x - track.x,
+            ..track
+        };
+        backend.paint_box(filled, &round(colors.primary, TRACK));
+        let knob_y = rect.y + (rect.height - KNOB) / 2.;
+        let knob = Rect::new(knob_x - KNOB / 2., knob_y, KNOB, KNOB);
+        backend.paint_box(knob, &round(colors.primary, KNOB));
+        backend.set_opacity(opacity);
+
+        ScreenHandle::new(Interaction {
+            enabled: self.enabled && interaction.enabled,
+            ..interaction
+        })
+    }
+
+    fn semantics(&self, _cx: &LayoutCx, rect: Rect) -> SemanticNode {
+        SemanticNode {
+            value: Some(self.value.get().to_string()),
+            enabled: self.enabled,
+            test_id: self.test_id.clone(),
+            ..SemanticNode::new(Role::Slider, rect)
+        }
+    }
+
+    fn respond(&self, _handle: &ScreenHandle) {
+        let Some(value) = self.dragged.take() else {
+            return;
+        };
+        if *self.value.read() == value {
+            return;
+        }
+        self.value.set(value);
+        if let Some(on_change) = &self.on_change {
+            (on_change.borrow_mut())(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Slider;
+    use crate::store::Store;
+
+    #[test]
+    fn values_on_steps_within_the_range() {
+        let slider = Slider::new(Store::new(0.), 10.0..=20.0).step(4.);
+        let data = slider.0.borrow();
+        assert_eq!(data.value_at(0.), 10.);
+        assert_eq!(data.value_at(0.3), 14.);
+        assert_eq!(data.value_at(0.5), 14.);
+        assert_eq!(data.value_at(0.65), 18.);
+        // The end of the range is not on a step, the last step is past the end
+        assert_eq!(data.value_at(1.), 20.);
+        assert_eq!(data.value_at(2.), 20.);
+        assert_eq!(data.fraction(15.), 0.5);
+    }
+
+    #[cfg(feature = "egui")]
+    #[test]
+    fn too_narrow_to_drag() {
+        use super::{SliderData, KNOB};
+        use crate::{
+            drawables::traits::{Sizing, TestId},
+            layout::tests::egui_frame,
+        };
+        use egui::{pos2, Context, Event, PointerButton};
+        use std::cell::Cell;
+
+        let ctx = Context::default();
+        let value = Store::new(0.5);
+        let track = Cell::new(None);
+        let frame = |events| {
+            egui_frame(&ctx, None, events, |screen| {
+                screen.slider(&value, 0.0..=1.0).width(10).test_id("narrow");
+                let rect = screen.semantics().find_by_test_id("narrow").unwrap().bounds;
+                track.set(Some(SliderData::track(rect)));
+            });
+        };
+        frame(vec![]);
+        let track = track.get().unwrap();
+        assert_eq!(track.width, 0.);
+
+        // On the start of the track, where the fraction would be 0 / 0
+        let pos = pos2(track.x, track.y);
+        let press = |pressed| Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        frame(vec![Event::PointerMoved(pos), press(true)]);
+        frame(vec![Event::PointerMoved(pos2(pos.x + KNOB, pos.y))]);
+        frame(vec![press(false)]);
+        assert_eq!(value.get(), 0.5);
+    }
+}
@@ -0,0 +1,207 @@
+use super::traits::{common_modifiers, Draw};
+use crate::{
+    backend::TextRun,
+    color::Color,
+    gestures::GestureHandlers,
+    layout::{LayoutCx, LayoutParams, Rect, Size},
+    screen::{Screen, ScreenHandle},
+    semantics::{Role, SemanticNode},
+    style::Style,
+    theme::{TextStyle, Theme},
+};
+use std::{cell::RefCell, rc::Rc};
+
+#[derive(Clone)]
+pub struct Text(pub(crate) Rc<RefCell<TextData>>);
+
+impl Text {
+    pub(crate) fn new(text: String) -> Self {
+        let data = TextData {
+            text,
+            ..Default::default()
+        };
+        Self(Rc::new(RefCell::new(data)))
+    }
+
+    pub fn size(self, size: u16) -> Self {
+        self.0.borrow_mut().size = Some(size.into());
+        self
+    }
+    /// The size and weight of this style in the theme, unless set
+    pub fn style(self, style: TextStyle) -> Self {
+        self.0.borrow_mut().text_style = style;
+        self
+    }
+    pub fn weight(self, weight: FontWeight) -> Self {
+        self.0.borrow_mut().weight = Some(weight);
+        self
+    }
+    pub fn color(self, color: Color) -> Self {
+        self.0.borrow_mut().color = Some(color);
+        self
+    }
+    pub fn align(self, align: TextAlign) -> Self {
+        self.0.borrow_mut().align = align;
+        self
+    }
+    /// Wrap at the width the layout allows. When off the text stays on one line.
+    pub fn wrap(self, wrap: bool) -> Self {
+        self.0.borrow_mut().wrap = wrap;
+        self
+    }
+    /// Cut the text off with an ellipsis after this many lines. Without `wrap` the line is cut
+    /// off at the width the layout allows.
+    pub fn max_lines(self, max_lines: u16) -> Self {
+        self.0.borrow_mut().max_lines = max_lines.into();
+        self
+    }
+    pub fn selectable(self, selectable: bool) -> Self {
+        self.0.borrow_mut().selectable = selectable;
+        self
+    }
+}
+
+common_modifiers!(Text);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FontWeight {
+    Light,
+    #[default]
+    Regular,
+    Bold,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Clone)]
+pub(crate) struct TextData {
+    pub text: String,
+    pub text_style: TextStyle,
+    pub size: Option<f32>,
+    pub weight: Option<FontWeight>,
+    pub color: Option<Color>,
+    pub align: TextAlign,
+    pub wrap: bool,
+    pub max_lines: usize,
+    pub selectable: bool,
+    pub layout: LayoutParams,
+    pub test_id: Option<String>,
+    pub gestures: GestureHandlers,
+    pub style: Style,
+    pub theme: Rc<Theme>,
+}
+
+impl Default for TextData {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            text_style: TextStyle::Body,
+            size: None,
+            weight: None,
+            color: None,
+            align: TextAlign::Left,
+            wrap: true,
+            max_lines: 0,
+            selectable: false,
+            layout: LayoutParams::default(),
+            test_id: None,
+            gestures: GestureHandlers::default(),
+            style: Style::default(),
+            theme: Rc::default(),
+        }
+    }
+}
+
+impl TextData {
+    fn run(&self) -> TextRun<'_> {
+        // What is not set comes from the theme
+        let font = self.theme.typography.font(self.text_style);
+        let weight = self.weight.unwrap_or(font.weight);
+        TextRun {
+            text: &self.text,
+            size: Some(self.size.unwrap_or(font.size)),
+            weight,
+            color: Some(self.color.unwrap_or_else(|| self.theme.text_color(weight))),
+            align: self.align,
+            wrap: self.wrap,
+            max_lines: self.max_lines,
+            selectable: self.selectable,
+        }
+    }
+}
+
+impl Draw for TextData {
+    fn params(&self) -> LayoutParams {
+        self.layout
+    }
+
+    fn style(&self) -> Style {
+        self.style
+    }
+
+    fn gestures(&self) -> Option<&GestureHandlers> {
+        Some(&self.gestures)
+    }
+
+    fn measure(&self, cx: &LayoutCx, max: Size) -> Size {
+        let size = cx.backend.measure_text(&self.run(), max);
+        // Aligned text needs room to move in
+        let width = match self.align {
+            TextAlign::Left => size.width,
+            TextAlign::Center | TextAlign::Right if max.width.is_finite() => max.width,
+            TextAlign::Center | TextAlign::Right => size.width,
+        };
+        Size::new(width, size.height)
+    }
+
+    fn draw(&self, screen: &mut Screen, rect: Rect) -> ScreenHandle {
+        let interaction = screen.backend().text(rect, &self.run());
+
+        ScreenHandle::new(interaction)
+    }
+
+    fn semantics(&self, _cx: &LayoutCx, rect: Rect) -> SemanticNode {
+        SemanticNode {
+            label: Some(self.text.clone()),
+            test_id: self.test_id.clone(),
+            ..SemanticNode::new(Role::Text, rect)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "egui"))]
+mod tests {
+    use crate::{
+        backend::TextRun,
+        layout::{tests::egui_frame, Size},
+        theme::Theme,
+    };
+    use egui::Context;
+
+    #[test]
+    fn max_lines_cuts_off_a_line_that_does_not_wrap() {
+        let long = "A line that is a lot wider than the screen it is shown on ".repeat(4);
+        let mut sizes = vec![];
+        egui_frame(&Context::default(), None, vec![], |screen| {
+            let max = Size::new(300., f32::INFINITY);
+            for max_lines in [0, 1] {
+                let text = TextRun {
+                    max_lines,
+                    ..TextRun::body(&long, &Theme::default())
+                };
+                sizes.push(screen.backend().measure_text(&text, max));
+            }
+        });
+        let (overflow, cut) = (sizes[0], sizes[1]);
+        assert!(overflow.width > 300.);
+        assert!(cut.width <= 300.);
+        // Still one line
+        assert_eq!(cut.height, overflow.height);
+    }
+}
@@ -0,0 +1,378 @@
+use super::traits::{common_modifiers, Draw};
+use crate::{
+    backend::TextFieldRun,
+    gestures::GestureHandlers,
+    layout::{LayoutCx, LayoutParams, Rect, Size},
+    screen::{Screen, ScreenHandle},
+    semantics::{Role, SemanticNode},
+    store::Store,
+    style::Style,
+    theme::Theme,
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// What the text is, so a backend with a soft keyboard can show the right one
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Keyboard {
+    #[default]
+    Text,
+    /// Digits only
+    Number,
+    /// Digits and a decimal separator
+    Decimal,
+    Email,
+    Phone,
+    Url,
+}
+
+/// An editable text, bound to a store. Drawing happens after `Page::show` returns, so edits
+/// are written to the store at the end of the frame, before the callbacks run.
+#[derive(Clone)]
+pub struct TextField(pub(crate) Rc<RefCell<TextFieldData>>);
+
+impl TextField {
+    pub(crate) fn new(value: Store<String>) -> Self {
+        Self(Rc::new(RefCell::new(TextFieldData::new(value))))
+    }
+
+    /// Shown in a weak color while the text is empty
+    pub fn placeholder(self, placeholder: impl Into<String>) -> Self {
+        self.0.borrow_mut().placeholder = placeholder.into();
+        self
+    }
+    /// Hides the characters, as for a password
+    pub fn secure(self, secure: bool) -> Self {
+        self.0.borrow_mut().secure = secure;
+        self
+    }
+    pub fn keyboard(self, keyboard: Keyboard) -> Self {
+        self.0.borrow_mut().keyboard = keyboard;
+        self
+    }
+    /// In characters. Typing or pasting more inserts only what fits.
+    pub fn max_length(self, max_length: usize) -> Self {
+        self.0.borrow_mut().max_length = Some(max_length);
+        self
+    }
+    /// Text with line breaks, with room for `rows` lines. It has no submit.
+    pub fn multiline(self, rows: u16) -> Self {
+        self.0.borrow_mut().rows = rows.max(1).into();
+        self
+    }
+    pub fn enabled(self, enabled: bool) -> Self {
+        self.0.borrow_mut().enabled = enabled;
+        self
+    }
+    /// Called with the new text after every edit
+    pub fn on_change(self, on_change: impl FnMut(&str) + 'static) -> Self {
+        self.0.borrow_mut().on_change = Some(RefCell::new(Box::new(on_change)));
+        self
+    }
+    /// Called with the text when the user presses enter in a single line field
+    pub fn on_submit(self, on_submit: impl FnMut(&str) + 'static) -> Self {
+        self.0.borrow_mut().on_submit = Some(RefCell::new(Box::new(on_submit)));
+        self
+    }
+    /// Names the field within `focus`, which moves the keyboard to it on request
+    pub fn focus(self, focus: &Focus, name: impl Into<String>) -> Self {
+        self.0.borrow_mut().focus = Some((focus.clone(), name.into()));
+        self
+    }
+}
+
+common_modifiers!(TextField);
+
+/// Moves the keyboard between the text fields that were named with `TextField::focus`.
+/// Clones refer to the same focus.
+#[derive(Clone, Default)]
+pub struct Focus(Store<FocusState>);
+
+#[derive(Default)]
+struct FocusState {
+    // The field that has the keyboard
+    current: Option<String>,
+    // Some(None) takes the keyboard away
+    requested: Option<Option<String>>,
+}
+
+impl Focus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gives the keyboard to the field with this name when it is drawn next
+    pub fn request(&self, name: impl Into<String>) {
+        let name = name.into();
+        self.0.update(|state| state.requested = Some(Some(name)));
+    }
+
+    /// Takes the keyboard away from the field that has it, which hides a soft keyboard
+    pub fn clear(&self) {
+        self.0.update(|state| state.requested = Some(None));
+    }
+
+    /// The name of the field that has the keyboard
+    pub fn current(&self) -> Option<String> {
+        self.0.read().current.clone()
+    }
+
+    // Whether the field should take or give up the keyboard in this frame
+    fn requests(&self, name: &str) -> (bool, bool) {
+        let state = self.0.read();
+        match &state.requested {
+            Some(Some(requested)) => (requested == name, false),
+            Some(None) => (false, state.current.as_deref() == Some(name)),
+            None => (false, false),
+        }
+    }
+
+    // Keeps track of the field after it was drawn. Only changes are written, as every change
+    // makes the runner draw again.
+    fn drawn(&self, name: &str, focused: bool) {
+        let state = self.0.read();
+        let is_current = state.current.as_deref() == Some(name);
+        let done = match &state.requested {
+            Some(Some(requested)) => requested == name && focused,
+            Some(None) => !focused && (is_current || state.current.is_none()),
+            None => false,
+        };
+        let current = if focused {
+            Some(Some(name.to_string())).filter(|_| !is_current)
+        } else {
+            is_current.then_some(None)
+        };
+        drop(state);
+
+        if done || current.is_some() {
+            self.0.update(|state| {
+                if done {
+                    state.requested = None;
+                }
+                if let Some(current) = current {
+                    state.current = current;
+                }
+            });
+        }
+    }
+}
+
+type Callback = RefCell<Box<dyn FnMut(&str)>>;
+
+pub(crate) struct TextFieldData {
+    pub value: Store<String>,
+    pub placeholder: String,
+    pub secure: bool,
+    pub keyboard: Keyboard,
+    pub max_length: Option<usize>,
+    pub rows: usize,
+    pub enabled: bool,
+    pub on_change: Option<Callback>,
+    pub on_submit: Option<Callback>,
+    pub focus: Option<(Focus, String)>,
+    pub layout: LayoutParams,
+    pub test_id: Option<String>,
+    pub gestures: GestureHandlers,
+    pub style: Style,
+    pub theme: Rc<Theme>,
+    // The text as edited while drawing, written to the value in `respond`
+    edited: RefCell<Option<String>>,
+}
+
+impl TextFieldData {
+    fn new(value: Store<String>) -> Self {
+        Self {
+            value,
+            placeholder: String::new(),
+            secure: false,
+            keyboard: Keyboard::Text,
+            max_length: None,
+            rows: 1,
+            enabled: true,
+            on_change: None,
+            on_submit: None,
+            focus: None,
+            layout: LayoutParams::default(),
+            test_id: None,
+            gestures: GestureHandlers::default(),
+            style: Style::default(),
+            theme: Rc::default(),
+            edited: RefCell::new(None),
+        }
+    }
+
+    fn run(&self) -> TextFieldRun<'_> {
+        let (request_focus, surrender_focus) = match &self.focus {
+            Some((focus, name)) => focus.requests(name),
+            None => (false, false),
+        };
+        TextFieldRun {
+            placeholder: &self.placeholder,
+            secure: self.secure,
+            keyboard: self.keyboard,
+            max_length: self.max_length,
+            rows: self.rows,
+            enabled: self.enabled,
+            color: self.theme.colors.text,
+            id: self
+                .focus
+                .as_ref()
+                .map(|(_, name)| name.as_str())
+                .or(self.test_id.as_deref()),
+            request_focus,
+            surrender_focus,
+        }
+    }
+}
+
+impl Draw for TextFieldData {
+    fn params(&self) -> LayoutParams {
+        self.layout
+    }
+
+    fn style(&self) -> Style {
+        self.style
+    }
+
+    fn gestures(&self) -> Option<&GestureHandlers> {
+        Some(&self.gestures)
+    }
+
+    fn measure(&self, cx: &LayoutCx, max: Size) -> Size {
+        cx.backend.measure_text_field(&self.run(), max)
+    }
+
+    fn draw(&self, screen: &mut Screen, rect: Rect) -> ScreenHandle {
+        let mut text = self.value.get();
+        let interaction = screen.backend().text_field(rect, &self.run(), &mut text);
+        if interaction.changed {
+            *self.edited.borrow_mut() = Some(text);
+        }
+        ScreenHandle::new(interaction)
+    }
+
+    fn semantics(&self, _cx: &LayoutCx, rect: Rect) -> SemanticNode {
+        let text = self.value.get();
+        let value = if self.secure {
+            "•".repeat(text.chars().count())
+        } else {
+            text
+        };
+        SemanticNode {
+            label: Some(self.placeholder.clone()).filter(|label| !label.is_empty()),
+            value: Some(value),
+            enabled: self.enabled,
+            test_id: self.test_id.clone(),
+            ..SemanticNode::new(Role::TextField, rect)
+        }
+    }
+
+    fn respond(&self, handle: &ScreenHandle) {
+        if let Some((focus, name)) = &self.focus {
+            focus.drawn(name, handle.focused());
+        }
+        if !handle.enabled() {
+            return;
+        }
+        if let Some(text) = self.edited.take() {
+            if *self.value.read() != text {
+                self.value.set(text.clone());
+                if let Some(on_change) = &self.on_change {
+                    (on_change.borrow_mut())(&text);
+                }
+            }
+        }
+        if handle.submitted() {
+            if let Some(on_submit) = &self.on_submit {
+                (on_submit.borrow_mut())(&self.value.get());
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "egui"))]
+mod tests {
+    use super::Focus;
+    use crate::{layout::tests::egui_frame, store::Store};
+    use egui::{pos2, Context, Event, Key, PointerButton};
+    use std::{cell::RefCell, rc::Rc};
+
+    struct Form {
+        name: Store<String>,
+        pin: Store<String>,
+        focus: Focus,
+        submitted: Rc<RefCell<Vec<String>>>,
+    }
+
+    // Runs one frame with a name field and a pin field below it
+    fn frame(ctx: &Context, form: &Form, events: Vec<Event>) {
+        egui_frame(ctx, None, events, |screen| {
+            let (focus, submitted) = (form.focus.clone(), form.submitted.clone());
+            screen
+                .text_field(&form.name)
+                .placeholder("Name")
+                .focus(&form.focus, "name")
+                .on_submit(move |name| {
+                    submitted.borrow_mut().push(name.to_string());
+                    focus.request("pin");
+                });
+            screen
+                .text_field(&form.pin)
+                .secure(true)
+                .max_length(4)
+                .focus(&form.focus, "pin");
+        });
+    }
+
+    fn key(key: Key) -> Vec<Event> {
+        [true, false]
+            .map(|pressed| Event::Key {
+                key,
+                pressed,
+                repeat: false,
+                modifiers: Default::default(),
+            })
+            .into()
+    }
+
+    #[test]
+    fn type_submit_and_move_focus() {
+        let ctx = Context::default();
+        let form = Form {
+            name: Store::default(),
+            pin: Store::default(),
+            focus: Focus::new(),
+            submitted: Rc::default(),
+        };
+
+        let pos = pos2(20., 15.);
+        let click = [true, false].map(|pressed| Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        });
+        frame(
+            &ctx,
+            &form,
+            vec![Event::PointerMoved(pos), click[0].clone()],
+        );
+        frame(&ctx, &form, vec![click[1].clone()]);
+        assert_eq!(form.focus.current().as_deref(), Some("name"));
+
+        frame(&ctx, &form, vec![Event::Text("Ada".to_string())]);
+        assert_eq!(form.name.get(), "Ada");
+
+        // Submitting moves the keyboard to the pin, where the text is cut off at 4
+        frame(&ctx, &form, key(Key::Enter));
+        frame(&ctx, &form, vec![]);
+        assert_eq!(*form.submitted.borrow(), ["Ada"]);
+        assert_eq!(form.focus.current().as_deref(), Some("pin"));
+        frame(&ctx, &form, vec![Event::Text("123456".to_string())]);
+        assert_eq!(form.pin.get(), "1234");
+
+        form.focus.clear();
+        frame(&ctx, &form, vec![]);
+        frame(&ctx, &form, vec![]);
+        assert_eq!(form.focus.current(), None);
+    }
+}
@@ -0,0 +1,293 @@
+use super::traits::{common_modifiers, Draw};
+use crate::{
+    backend::{Backend, Interaction, Sense, TextRun},
+    color::Color,
+    gestures::GestureHandlers,
+    layout::{LayoutCx, LayoutParams, Rect, Size},
+    screen::{Screen, ScreenHandle},
+    semantics::{Role, SemanticNode},
+    store::Store,
+    style::{Border, Style},
+    theme::Theme,
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// Of the controls that are disabled
+pub(crate) const DISABLED_OPACITY: f32 = 0.4;
+
+const SWITCH: Size = Size::new(36., 20.);
+const CHECKBOX: Size = Size::new(20., 20.);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ToggleKind {
+    #[default]
+    Switch,
+    Checkbox,
+}
+
+/// A switch or a checkbox with a label, bound to a store. A click anywhere on it flips the
+/// value at the end of the frame, before the callbacks run.
+#[derive(Clone)]
+pub struct Toggle(pub(crate) Rc<RefCell<ToggleData>>);
+
+impl Toggle {
+    pub(crate) fn new(kind: ToggleKind, value: Store<bool>, label: String) -> Self {
+        Self(Rc::new(RefCell::new(ToggleData {
+            kind,
+            value,
+            label,
+            enabled: true,
+            on_change: None,
+            layout: LayoutParams::default(),
+            test_id: None,
+            gestures: GestureHandlers::default(),
+            style: Style::default(),
+            theme: Rc::default(),
+        })))
+    }
+
+    /// A disabled toggle is faded and does not change
+    pub fn enabled(self, enabled: bool) -> Self {
+        self.0.borrow_mut().enabled = enabled;
+        self
+    }
+    /// Called with the new value
+    pub fn on_change(self, on_change: impl FnMut(bool) + 'static) -> Self {
+        self.0.borrow_mut().on_change = Some(RefCell::new(Box::new(on_change)));
+        self
+    }
+}
+
+common_modifiers!(Toggle);
+
+pub(crate) struct ToggleData {
+    pub kind: ToggleKind,
+    pub value: Store<bool>,
+    pub label: String,
+    pub enabled: bool,
+    pub on_change: Option<Callback>,
+    pub layout: LayoutParams,
+    pub test_id: Option<String>,
+    pub gestures: GestureHandlers,
+    pub style: Style,
+    pub theme: Rc<Theme>,
+}
+
+type Callback = RefCell<Box<dyn FnMut(bool)>>;
+
+impl ToggleData {
+    fn control(&self) -> Size {
+        match self.kind {
+            ToggleKind::Switch => SWITCH,
+            ToggleKind::Checkbox => CHECKBOX,
+        }
+    }
+
+    fn paint_control(&self, backend: &mut dyn Backend, rect: Rect, on: bool) {
+        let colors = &self.theme.colors;
+        match self.kind {
+            ToggleKind::Switch => {
+                let track = if on { colors.primary } else { colors.separator };
+                backend.paint_box(rect, &round(track, rect.height));
+                let knob = rect.height - 4.;
+                let x = if on {
+                    rect.max_x() - 2. - knob
+                } else {
+                    rect.x + 2.
+                };
+                let knob = Rect::new(x, rect.y + 2., knob, knob);
+                backend.paint_box(knob, &round(colors.background, knob.height));
+            }
+            ToggleKind::Checkbox => {
+                let mut style = Style {
+                    rounding: 4.,
+                    ..Style::default()
+                };
+                if on {
+                    style.background = Some(colors.primary);
+                    backend.paint_box(rect, &style);
+                    let (x, y) = (rect.x, rect.y);
+                    let check = [(x + 5., y + 10.), (x + 9., y + 14.), (x + 15., y + 6.)];
+                    backend.paint_path(&check, 2., colors.on_primary);
+                } else {
+                    style.border = Some(Border {
+                        width: 2.,
+                        color: colors.separator,
+                    });
+                    backend.paint_box(rect, &style);
+                }
+            }
+        }
+    }
+}
+
+impl Draw for ToggleData {
+    fn params(&self) -> LayoutParams {
+        self.layout
+    }
+
+    fn style(&self) -> Style {
+        self.style
+    }
+
+    fn gestures(&self) -> Option<&GestureHandlers> {
+        Some(&self.gestures)
+    }
+
+    fn measure(&self, cx: &LayoutCx, max: Size) -> Size {
+        measure_labeled(cx.backend, &self.theme, self.control(), &self.label, max)
+    }
+
+    fn draw(&self, screen: &mut Screen, rect: Rect) -> ScreenHandle {
+        let backend = screen.backend();
+        let control = self.control();
+        let on = *self.value.read();
+        let interaction = draw_labeled(
+            backend,
+            &self.theme,
+            rect,
+            control,
+            &self.label,
+            self.enabled,
+            |backend, rect| self.paint_control(backend, rect, on),
+        );
+        ScreenHandle::new(interaction)
+    }
+
+    fn semantics(&self, _cx: &LayoutCx, rect: Rect) -> SemanticNode {
+        let role = match self.kind {
+            ToggleKind::Switch => Role::Switch,
+            ToggleKind::Checkbox => Role::Checkbox,
+        };
+        SemanticNode {
+            label: Some(self.label.clone()),
+            checked: Some(*self.value.read()),
+            enabled: self.enabled,
+            test_id: self.test_id.clone(),
+            ..SemanticNode::new(role, rect)
+        }
+    }
+
+    fn respond(&self, handle: &ScreenHandle) {
+        if !handle.enabled() || !handle.clicked() {
+            return;
+        }
+        let on = self.value.update(|on| {
+            *on = !*on;
+            *on
+        });
+        if let Some(on_change) = &self.on_change {
+            (on_change.borrow_mut())(on);
+        }
+    }
+}
+
+// A background with round ends
+fn round(color: Color, height: f32) -> Style {
+    Style {
+        background: Some(color),
+        rounding: height / 2.,
+        ..Style::default()
+    }
+}
+
+/// The size of a control with its label to the right of it
+pub(crate) fn measure_labeled(
+    backend: &dyn Backend,
+    theme: &Theme,
+    control: Size,
+    label: &str,
+    max: Size,
+) -> Size {
+    if label.is_empty() {
+        return control;
+    }
+    let text = backend.measure_text(&TextRun::body(label, theme), max);
+    let gap = f32::from(theme.spacing.m);
+    Size::new(
+        control.width + gap + text.width,
+        control.height.max(text.height),
+    )
+}
+
+/// Draws a control of size `control` at the left of `rect` with `paint`, and its label. A
+/// click anywhere on `rect` counts, also on the label.
+pub(crate) fn draw_labeled(
+    backend: &mut dyn Backend,
+    theme: &Theme,
+    rect: Rect,
+    control: Size,
+    label: &str,
+    enabled: bool,
+    paint: impl FnOnce(&mut dyn Backend, Rect),
+) -> Interaction {
+    let opacity = backend.opacity();
+    if !enabled {
+        backend.set_opacity(opacity * DISABLED_OPACITY);
+    }
+    let control_rect = Rect::from_size(
+        rect.x,
+        rect.y + (rect.height - control.height) / 2.,
+        control,
+    );
+    if !label.is_empty() {
+        let run = TextRun::body(label, theme);
+        let text = backend.measure_text(&run, rect.size());
+        let x = control_rect.max_x() + f32::from(theme.spacing.m);
+        let y = rect.y + (rect.height - text.height) / 2.;
+        backend.text(Rect::new(x, y, rect.max_x() - x, text.height), &run);
+    }
+    paint(backend, control_rect);
+    backend.set_opacity(opacity);
+
+    // Last, so it gets the clicks instead of the label
+    let interaction = backend.interact(rect, Sense::Click);
+    Interaction {
+        enabled: enabled && interaction.enabled,
+        ..interaction
+    }
+}
+
+#[cfg(all(test, feature = "egui"))]
+mod tests {
+    use crate::{layout::tests::egui_frame, store::Store};
+    use egui::{pos2, Context, Event, PointerButton};
+
+    // Runs one frame with a switch above a disabled checkbox, pressing or releasing at `pos`
+    fn frame(ctx: &Context, wifi: &Store<bool>, pressed: Option<bool>, pos: (f32, f32)) {
+        let pos = pos2(pos.0, pos.1);
+        let mut events = vec![Event::PointerMoved(pos)];
+        if let Some(pressed) = pressed {
+            events.push(Event::PointerButton {
+                pos,
+                button: PointerButton::Primary,
+                pressed,
+                modifiers: Default::default(),
+            });
+        }
+        egui_frame(ctx, None, events, |screen| {
+            screen.toggle(wifi, "Wi-Fi");
+            screen.checkbox(wifi, "Locked").enabled(false);
+        });
+    }
+
+    #[test]
+    fn clicks_flip_the_value() {
+        let ctx = Context::default();
+        let wifi = Store::new(false);
+        let click = |pos| {
+            frame(&ctx, &wifi, None, pos);
+            frame(&ctx, &wifi, Some(true), pos);
+            frame(&ctx, &wifi, Some(false), pos);
+        };
+
+        // On the label of the switch
+        click((80., 18.));
+        assert!(wifi.get());
+        click((20., 18.));
+        assert!(!wifi.get());
+        // The disabled checkbox below it
+        click((20., 42.));
+        assert!(!wifi.get());
+    }
+}
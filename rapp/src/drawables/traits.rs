@@ -1,10 +1,195 @@
-use crate::screen::{Screen, ScreenHandle};
-use egui::{Response, Ui};
+use crate::{
+    color::Color,
+    gestures::{Gesture, GestureHandlers, GestureKind},
+    layout::{LayoutCx, LayoutParams, Length, Rect, Size},
+    screen::{Screen, ScreenHandle},
+    semantics::{Role, SemanticNode},
+    style::{Border, Insets, Shadow, Style, Visibility},
+};
+use std::cell::RefMut;
+
+/// Implements `Sizing`, `Styled`, `TestId` and `Gestures` for a drawable whose data has the
+/// fields `layout`, `style`, `test_id` and `gestures`. Generic drawables pass their parameters,
+/// as in `common_modifiers!(impl<T> RadioGroup<T>)`.
+macro_rules! common_modifiers {
+    (impl<$($param:ident),*> $drawable:ty) => {
+        impl<$($param),*> $crate::drawables::traits::Sizing for $drawable {
+            fn layout_params(&self) -> std::cell::RefMut<'_, $crate::layout::LayoutParams> {
+                std::cell::RefMut::map(self.0.borrow_mut(), |data| &mut data.layout)
+            }
+        }
+
+        impl<$($param),*> $crate::drawables::traits::Styled for $drawable {
+            fn style_mut(&self) -> std::cell::RefMut<'_, $crate::style::Style> {
+                std::cell::RefMut::map(self.0.borrow_mut(), |data| &mut data.style)
+            }
+        }
+
+        impl<$($param),*> $crate::drawables::traits::TestId for $drawable {
+            fn test_id_mut(&self) -> std::cell::RefMut<'_, Option<String>> {
+                std::cell::RefMut::map(self.0.borrow_mut(), |data| &mut data.test_id)
+            }
+        }
+
+        impl<$($param),*> $crate::drawables::traits::Gestures for $drawable {
+            fn gestures_mut(&self) -> std::cell::RefMut<'_, $crate::gestures::GestureHandlers> {
+                std::cell::RefMut::map(self.0.borrow_mut(), |data| &mut data.gestures)
+            }
+        }
+    };
+    ($drawable:ty) => {
+        common_modifiers!(impl<> $drawable);
+    };
+}
+
+pub(crate) use common_modifiers;
 
 pub trait Draw {
-    fn draw(&self, screen: &mut Screen) -> ScreenHandle;
+    /// How the parent should size it
+    fn params(&self) -> LayoutParams;
+
+    /// Padding, background and the other modifiers, which the layout applies
+    fn style(&self) -> Style {
+        Style::default()
+    }
+
+    /// The size of the content when it may use up to `max`. `max` can be infinite.
+    fn measure(&self, cx: &LayoutCx, max: Size) -> Size;
+
+    /// Draws the content at the place the layout gave it, within the padding
+    fn draw(&self, screen: &mut Screen, rect: Rect) -> ScreenHandle;
+
+    /// Called after the whole screen is drawn, with the handle returned by `draw`
+    fn respond(&self, _handle: &ScreenHandle) {}
+
+    /// What tests see of it when the layout places it at `rect`
+    fn semantics(&self, _cx: &LayoutCx, rect: Rect) -> SemanticNode {
+        SemanticNode::new(Role::Custom, rect)
+    }
+
+    /// The gestures to recognize on it, which the layout does
+    fn gestures(&self) -> Option<&GestureHandlers> {
+        None
+    }
+}
+
+/// Layout builder methods shared by all drawables
+pub trait Sizing: Sized {
+    fn layout_params(&self) -> RefMut<'_, LayoutParams>;
+
+    fn width(self, width: impl Into<Length>) -> Self {
+        self.layout_params().width = width.into();
+        self
+    }
+    fn height(self, height: impl Into<Length>) -> Self {
+        self.layout_params().height = height.into();
+        self
+    }
+    /// Take the width that the parent has left
+    fn fill_width(self) -> Self {
+        self.width(Length::MatchParent)
+    }
+    /// Take the height that the parent has left
+    fn fill_height(self) -> Self {
+        self.height(Length::MatchParent)
+    }
+    /// Share of the space that is left in a column or row, relative to the other weights
+    fn weight(self, weight: f32) -> Self {
+        self.layout_params().weight = weight;
+        self
+    }
+    fn min_width(self, min_width: u16) -> Self {
+        self.layout_params().min_width = min_width.into();
+        self
+    }
+    fn max_width(self, max_width: u16) -> Self {
+        self.layout_params().max_width = max_width.into();
+        self
+    }
+    fn min_height(self, min_height: u16) -> Self {
+        self.layout_params().min_height = min_height.into();
+        self
+    }
+    fn max_height(self, max_height: u16) -> Self {
+        self.layout_params().max_height = max_height.into();
+        self
+    }
+    /// Width divided by height, used for the side that is not set
+    fn aspect_ratio(self, aspect_ratio: f32) -> Self {
+        self.layout_params().aspect_ratio = Some(aspect_ratio);
+        self
+    }
 }
 
-pub(crate) trait EguiDraw {
-    fn draw_egui(&self, ui: &mut Ui) -> Response;
+/// Style builder methods shared by all drawables
+pub trait Styled: Sized {
+    fn style_mut(&self) -> RefMut<'_, Style>;
+
+    /// Space between the edge and the content, within the size
+    fn padding(self, padding: impl Into<Insets>) -> Self {
+        self.style_mut().padding = padding.into();
+        self
+    }
+    /// Space around it, outside of the size
+    fn margin(self, margin: impl Into<Insets>) -> Self {
+        self.style_mut().margin = margin.into();
+        self
+    }
+    fn background(self, color: Color) -> Self {
+        self.style_mut().background = Some(color);
+        self
+    }
+    fn border(self, width: u16, color: Color) -> Self {
+        let width = width.into();
+        self.style_mut().border = Some(Border { width, color });
+        self
+    }
+    /// Corner radius of the background and the border
+    fn rounding(self, rounding: u16) -> Self {
+        self.style_mut().rounding = rounding.into();
+        self
+    }
+    fn opacity(self, opacity: f32) -> Self {
+        self.style_mut().opacity = opacity.clamp(0., 1.);
+        self
+    }
+    fn shadow(self, shadow: Shadow) -> Self {
+        self.style_mut().shadow = Some(shadow);
+        self
+    }
+    /// Hidden keeps its place, see `visibility` for more
+    fn visible(self, visible: bool) -> Self {
+        let visibility = if visible {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+        self.visibility(visibility)
+    }
+    fn visibility(self, visibility: Visibility) -> Self {
+        self.style_mut().visibility = visibility;
+        self
+    }
+}
+
+/// Builder method to find a drawable in tests, see `SemanticTree::find_by_test_id`
+pub trait TestId: Sized {
+    fn test_id_mut(&self) -> RefMut<'_, Option<String>>;
+
+    fn test_id(self, test_id: impl Into<String>) -> Self {
+        *self.test_id_mut() = Some(test_id.into());
+        self
+    }
+}
+
+/// Builder method to recognize gestures on any drawable
+pub trait Gestures: Sized {
+    fn gestures_mut(&self) -> RefMut<'_, GestureHandlers>;
+
+    /// Recognizes gestures of `kind` on it, also over what is drawn on top of it. They are
+    /// handed to `on_gesture` at the end of the frame, before the callbacks of the drawable.
+    fn on_gesture(self, kind: GestureKind, on_gesture: impl FnMut(&Gesture) + 'static) -> Self {
+        self.gestures_mut().add(kind, on_gesture);
+        self
+    }
 }
@@ -0,0 +1,366 @@
+//! Gestures that any drawable can recognize, see `Gestures::on_gesture`
+
+use crate::{backend::Pointer, layout::Rect};
+use std::{cell::RefCell, rc::Rc};
+
+/// How long a pointer has to stay down for a long press
+pub const LONG_PRESS_SECONDS: f64 = 0.5;
+/// How far the pointer moves before a press is a pan
+const SLOP: f32 = 8.;
+/// Most time and distance between the taps of a double tap
+const DOUBLE_TAP_SECONDS: f64 = 0.3;
+const DOUBLE_TAP_DISTANCE: f32 = 24.;
+/// Least speed of a pan that ends as a swipe, in points per second
+const SWIPE_SPEED: f32 = 300.;
+
+/// A gesture and where it happened. Positions are on the screen, like the rects of the layout.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gesture {
+    /// Held still for half a second
+    LongPress { pos: (f32, f32) },
+    /// Two taps close to each other
+    DoubleTap { pos: (f32, f32) },
+    /// A pan that was fast when it ended. The velocity is in points per second.
+    Swipe {
+        direction: SwipeDirection,
+        velocity: (f32, f32),
+    },
+    /// Dragging, reported in every frame in which the pointer moves. The delta is how far it
+    /// moved since the last report.
+    Pan {
+        phase: PanPhase,
+        pos: (f32, f32),
+        delta: (f32, f32),
+    },
+    /// Two fingers that move apart or turn around `center`. `scale` is relative and
+    /// `rotation` in radians, both since the last frame. In the viewer, ctrl+scroll pinches
+    /// and alt+scroll rotates.
+    Pinch {
+        center: (f32, f32),
+        scale: f32,
+        rotation: f32,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GestureKind {
+    LongPress,
+    DoubleTap,
+    Swipe,
+    Pan,
+    Pinch,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PanPhase {
+    Started,
+    Moved,
+    /// The pointer was released
+    Ended,
+}
+
+impl Gesture {
+    pub fn kind(&self) -> GestureKind {
+        match self {
+            Gesture::LongPress { .. } => GestureKind::LongPress,
+            Gesture::DoubleTap { .. } => GestureKind::DoubleTap,
+            Gesture::Swipe { .. } => GestureKind::Swipe,
+            Gesture::Pan { .. } => GestureKind::Pan,
+            Gesture::Pinch { .. } => GestureKind::Pinch,
+        }
+    }
+}
+
+/// The gestures a drawable recognizes, with what to call for them. Clones call the same
+/// handlers.
+#[derive(Clone, Default)]
+pub struct GestureHandlers(Vec<(GestureKind, Handler)>);
+
+type Handler = Rc<RefCell<Box<dyn FnMut(&Gesture)>>>;
+
+impl GestureHandlers {
+    pub(crate) fn add(&mut self, kind: GestureKind, handler: impl FnMut(&Gesture) + 'static) {
+        self.0
+            .push((kind, Rc::new(RefCell::new(Box::new(handler)))));
+    }
+
+    pub(crate) fn kinds(&self) -> Vec<GestureKind> {
+        self.0.iter().map(|(kind, _)| *kind).collect()
+    }
+
+    /// Calls the handlers with the gestures of their kind
+    pub(crate) fn respond(&self, gestures: &[Gesture]) {
+        for gesture in gestures {
+            for (kind, handler) in &self.0 {
+                if gesture.kind() == *kind {
+                    (handler.borrow_mut())(gesture);
+                }
+            }
+        }
+    }
+}
+
+/// What a backend reports in a frame for recognizing gestures
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GestureInput {
+    /// In seconds
+    pub time: f64,
+    pub pointer: Pointer,
+    /// How much two fingers moved apart in this frame, 1 when they did not
+    pub zoom: f32,
+    /// How far two fingers turned in this frame, in radians
+    pub rotation: f32,
+}
+
+/// Turns the input of the frames into gestures. Backends keep one for each drawable that
+/// recognizes gestures.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Recognizer {
+    press: Option<Press>,
+    // When and where the last tap was, for a double tap
+    tap: Option<(f64, (f32, f32))>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Press {
+    start: (f32, f32),
+    time: f64,
+    // Where the pointer was in the last frame
+    last: (f32, f32),
+    panning: bool,
+    long_pressed: bool,
+}
+
+impl Recognizer {
+    /// The gestures of `kinds` on `rect` in this frame. Gestures start with a press on
+    /// `rect`, and go on when the pointer leaves it.
+    pub fn update(
+        &mut self,
+        rect: Rect,
+        input: &GestureInput,
+        kinds: &[GestureKind],
+    ) -> Vec<Gesture> {
+        let pointer = input.pointer;
+        let over = pointer.pos.map_or(false, |pos| rect.contains(pos));
+        if let (true, true, Some(pos)) = (pointer.pressed, over, pointer.pos) {
+            self.press = Some(Press {
+                start: pos,
+                time: input.time,
+                last: pos,
+                panning: false,
+                long_pressed: false,
+            });
+        }
+
+        let mut gestures = vec![];
+        if let Some(mut press) = self.press {
+            let pos = pointer.pos.unwrap_or(press.last);
+            let moved = (pos.0 - press.start.0, pos.1 - press.start.1);
+            if !press.panning && moved.0.hypot(moved.1) > SLOP {
+                press.panning = true;
+                let (phase, delta) = (PanPhase::Started, moved);
+                gestures.push(Gesture::Pan { phase, pos, delta });
+            } else if press.panning && pos != press.last {
+                let phase = PanPhase::Moved;
+                let delta = (pos.0 - press.last.0, pos.1 - press.last.1);
+                gestures.push(Gesture::Pan { phase, pos, delta });
+            }
+            press.last = pos;
+
+            let held = input.time - press.time;
+            if !press.panning && !press.long_pressed && pointer.down && held >= LONG_PRESS_SECONDS {
+                press.long_pressed = true;
+                gestures.push(Gesture::LongPress { pos: press.start });
+            }
+
+            self.press = Some(press);
+            if pointer.released {
+                self.press = None;
+                gestures.extend(self.release(press, input.time, pos));
+            }
+        }
+
+        if let (true, Some(center)) = (over, pointer.pos) {
+            if input.zoom != 1. || input.rotation != 0. {
+                let (scale, rotation) = (input.zoom, input.rotation);
+                gestures.push(Gesture::Pinch {
+                    center,
+                    scale,
+                    rotation,
+                });
+            }
+        }
+        gestures.retain(|gesture| kinds.contains(&gesture.kind()));
+        gestures
+    }
+
+    /// When a press that is held still becomes a long press, to draw a frame then
+    pub fn long_press_due(&self) -> Option<f64> {
+        self.press
+            .filter(|press| !press.panning && !press.long_pressed)
+            .map(|press| press.time + LONG_PRESS_SECONDS)
+    }
+
+    fn release(&mut self, press: Press, time: f64, pos: (f32, f32)) -> Vec<Gesture> {
+        if press.panning {
+            let phase = PanPhase::Ended;
+            let mut gestures = vec![Gesture::Pan {
+                phase,
+                pos,
+                delta: (0., 0.),
+            }];
+            // At least a frame, for a press and release in the same frame
+            let seconds = (time - press.time).max(1. / 60.) as f32;
+            let (dx, dy) = (pos.0 - press.start.0, pos.1 - press.start.1);
+            let velocity = (dx / seconds, dy / seconds);
+            if velocity.0.hypot(velocity.1) >= SWIPE_SPEED {
+                let direction = match (dx.abs() > dy.abs(), dx > 0., dy > 0.) {
+                    (true, true, _) => SwipeDirection::Right,
+                    (true, false, _) => SwipeDirection::Left,
+                    (false, _, true) => SwipeDirection::Down,
+                    (false, _, false) => SwipeDirection::Up,
+                };
+                gestures.push(Gesture::Swipe {
+                    direction,
+                    velocity,
+                });
+            }
+            return gestures;
+        }
+        if press.long_pressed {
+            return vec![];
+        }
+
+        let double = self.tap.map_or(false, |(tap_time, tap)| {
+            let distance = (pos.0 - tap.0).hypot(pos.1 - tap.1);
+            time - tap_time <= DOUBLE_TAP_SECONDS && distance <= DOUBLE_TAP_DISTANCE
+        });
+        if double {
+            self.tap = None;
+            vec![Gesture::DoubleTap { pos }]
+        } else {
+            self.tap = Some((time, pos));
+            vec![]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Gesture, GestureInput, GestureKind, PanPhase, Recognizer, SwipeDirection,
+        LONG_PRESS_SECONDS,
+    };
+    use crate::{backend::Pointer, layout::Rect};
+
+    const ALL: [GestureKind; 5] = [
+        GestureKind::LongPress,
+        GestureKind::DoubleTap,
+        GestureKind::Swipe,
+        GestureKind::Pan,
+        GestureKind::Pinch,
+    ];
+
+    // Feeds the recognizer a frame at `time`
+    struct Frames {
+        recognizer: Recognizer,
+        rect: Rect,
+    }
+
+    impl Frames {
+        fn frame(
+            &mut self,
+            time: f64,
+            pos: (f32, f32),
+            pressed: bool,
+            down: bool,
+            released: bool,
+        ) -> Vec<Gesture> {
+            let input = GestureInput {
+                time,
+                pointer: Pointer {
+                    pos: Some(pos),
+                    down,
+                    pressed,
+                    released,
+                },
+                zoom: 1.,
+                rotation: 0.,
+            };
+            self.recognizer.update(self.rect, &input, &ALL)
+        }
+
+        fn tap(&mut self, time: f64, pos: (f32, f32)) -> Vec<Gesture> {
+            let mut gestures = self.frame(time, pos, true, true, false);
+            gestures.extend(self.frame(time + 0.05, pos, false, false, true));
+            gestures
+        }
+    }
+
+    fn frames() -> Frames {
+        Frames {
+            recognizer: Recognizer::default(),
+            rect: Rect::new(0., 0., 100., 100.),
+        }
+    }
+
+    #[test]
+    fn taps_and_long_presses() {
+        let mut frames = frames();
+        assert_eq!(frames.tap(0., (10., 10.)), vec![]);
+        assert_eq!(
+            frames.tap(0.2, (12., 10.)),
+            vec![Gesture::DoubleTap { pos: (12., 10.) }]
+        );
+        // Too late for a double tap, and outside of the rect
+        assert_eq!(frames.tap(1., (10., 10.)), vec![]);
+        assert_eq!(frames.tap(2., (10., 10.)), vec![]);
+        assert_eq!(frames.tap(2.2, (110., 10.)), vec![]);
+
+        assert_eq!(frames.frame(3., (50., 50.), true, true, false), vec![]);
+        assert_eq!(
+            frames.recognizer.long_press_due(),
+            Some(3. + LONG_PRESS_SECONDS)
+        );
+        let held = frames.frame(3.6, (52., 50.), false, true, false);
+        assert_eq!(held, vec![Gesture::LongPress { pos: (50., 50.) }]);
+        // Not a tap as well
+        assert_eq!(frames.frame(3.7, (52., 50.), false, false, true), vec![]);
+    }
+
+    #[test]
+    fn pans_that_end_in_a_swipe() {
+        let mut frames = frames();
+        frames.frame(0., (50., 50.), true, true, false);
+        // Within the slop
+        assert_eq!(frames.frame(0.02, (50., 45.), false, true, false), vec![]);
+        let started = frames.frame(0.04, (50., 30.), false, true, false);
+        let (phase, pos, delta) = (PanPhase::Started, (50., 30.), (0., -20.));
+        assert_eq!(started, vec![Gesture::Pan { phase, pos, delta }]);
+        // Outside of the rect
+        let moved = frames.frame(0.06, (50., -30.), false, true, false);
+        let (phase, pos, delta) = (PanPhase::Moved, (50., -30.), (0., -60.));
+        assert_eq!(moved, vec![Gesture::Pan { phase, pos, delta }]);
+
+        let ended = frames.frame(0.1, (50., -30.), false, false, true);
+        let (phase, pos, delta) = (PanPhase::Ended, (50., -30.), (0., 0.));
+        assert_eq!(ended[0], Gesture::Pan { phase, pos, delta });
+        let Gesture::Swipe {
+            direction,
+            velocity,
+        } = ended[1]
+        else {
+            panic!("no swipe in {ended:?}");
+        };
+        assert_eq!(direction, SwipeDirection::Up);
+        assert!((velocity.1 + 800.).abs() < 0.1);
+    }
+}
@@ -0,0 +1,548 @@
+use crate::{
+    backend::{Backend, Interaction},
+    drawables::traits::Draw,
+    gestures::GestureHandlers,
+    screen::{Screen, ScreenHandle},
+    semantics::{Role, SemanticNode},
+    style::Visibility,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Size {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Size {
+    pub const ZERO: Size = Size::new(0., 0.);
+    pub const INFINITY: Size = Size::new(f32::INFINITY, f32::INFINITY);
+
+    pub const fn new(width: f32, height: f32) -> Self {
+        Self { width, height }
+    }
+
+    /// Smaller by `amount` on every side, but never negative
+    pub fn shrink(self, amount: f32) -> Self {
+        Self::new(
+            (self.width - 2. * amount).max(0.),
+            (self.height - 2. * amount).max(0.),
+        )
+    }
+
+    pub fn expand(self, amount: f32) -> Self {
+        Self::new(self.width + 2. * amount, self.height + 2. * amount)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rect {
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn from_size(x: f32, y: f32, size: Size) -> Self {
+        Self::new(x, y, size.width, size.height)
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    pub fn max_x(&self) -> f32 {
+        self.x + self.width
+    }
+
+    pub fn max_y(&self) -> f32 {
+        self.y + self.height
+    }
+
+    /// Smaller by `amount` on every side, but never negative
+    pub fn shrink(self, amount: f32) -> Self {
+        let size = self.size().shrink(amount);
+        Self::from_size(self.x + amount, self.y + amount, size)
+    }
+
+    pub fn contains(&self, (x, y): (f32, f32)) -> bool {
+        x >= self.x && x < self.max_x() && y >= self.y && y < self.max_y()
+    }
+
+    /// The part that is also in `other`, empty when they do not overlap
+    pub fn intersect(self, other: Rect) -> Self {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let max_x = self.max_x().min(other.max_x()).max(x);
+        let max_y = self.max_y().min(other.max_y()).max(y);
+        Self::new(x, y, max_x - x, max_y - y)
+    }
+}
+
+#[cfg(feature = "egui")]
+impl From<Rect> for egui::Rect {
+    fn from(rect: Rect) -> Self {
+        egui::Rect::from_min_size([rect.x, rect.y].into(), [rect.width, rect.height].into())
+    }
+}
+
+#[cfg(feature = "egui")]
+impl From<egui::Vec2> for Size {
+    fn from(size: egui::Vec2) -> Self {
+        Self::new(size.x, size.y)
+    }
+}
+
+#[cfg(feature = "egui")]
+impl From<egui::Rect> for Rect {
+    fn from(rect: egui::Rect) -> Self {
+        Self::new(rect.min.x, rect.min.y, rect.width(), rect.height())
+    }
+}
+
+/// How large a drawable wants to be along one axis
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Length {
+    Fixed(f32),
+    /// As large as the content
+    #[default]
+    WrapContent,
+    /// As large as the parent allows. In a column or row this takes the space that is left.
+    MatchParent,
+}
+
+impl From<u16> for Length {
+    fn from(length: u16) -> Self {
+        Length::Fixed(length.into())
+    }
+}
+
+impl From<f32> for Length {
+    fn from(length: f32) -> Self {
+        Length::Fixed(length)
+    }
+}
+
+/// How a drawable is sized by its parent
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayoutParams {
+    pub width: Length,
+    pub height: Length,
+    /// Share of the space that is left in a column or row. 0 means no share.
+    pub weight: f32,
+    pub min_width: f32,
+    pub max_width: f32,
+    pub min_height: f32,
+    pub max_height: f32,
+    /// Width divided by height. Used when only one side is known.
+    pub aspect_ratio: Option<f32>,
+}
+
+impl Default for LayoutParams {
+    fn default() -> Self {
+        Self {
+            width: Length::WrapContent,
+            height: Length::WrapContent,
+            weight: 0.,
+            min_width: 0.,
+            max_width: f32::INFINITY,
+            min_height: 0.,
+            max_height: f32::INFINITY,
+            aspect_ratio: None,
+        }
+    }
+}
+
+impl LayoutParams {
+    pub fn fixed(width: f32, height: f32) -> Self {
+        Self {
+            width: Length::Fixed(width),
+            height: Length::Fixed(height),
+            ..Default::default()
+        }
+    }
+}
+
+/// The sizes a parent allows. `max` can be infinite, for example along a scroll direction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+    pub min: Size,
+    pub max: Size,
+}
+
+impl Constraints {
+    pub fn loose(max: Size) -> Self {
+        Self {
+            min: Size::ZERO,
+            max,
+        }
+    }
+
+    pub fn tight(size: Size) -> Self {
+        Self {
+            min: size,
+            max: size,
+        }
+    }
+}
+
+/// What measuring needs to know about the place where drawables end up, like the fonts
+pub struct LayoutCx<'a> {
+    pub(crate) backend: &'a dyn Backend,
+}
+
+impl<'a> LayoutCx<'a> {
+    pub fn new(backend: &'a dyn Backend) -> Self {
+        Self { backend }
+    }
+}
+
+/// The size of a drawable within `constraints`, following its layout params.
+///
+/// Fixed and match parent lengths are used as they are. Wrap content lengths ask the drawable
+/// to measure its content. An aspect ratio fills in a side that is not known otherwise.
+/// The result is kept within the min and max of both the params and the constraints, where
+/// the max wins.
+///
+/// The lengths include the padding of the style. The margin comes on top.
+pub fn measure(drawable: &dyn Draw, cx: &LayoutCx, constraints: Constraints) -> Size {
+    let style = drawable.style();
+    if style.visibility == Visibility::Gone {
+        return Size::ZERO;
+    }
+    let margin = style.margin;
+    let constraints = Constraints {
+        min: margin.shrink_size(constraints.min),
+        max: margin.shrink_size(constraints.max),
+    };
+    margin.expand_size(measure_box(drawable, cx, constraints))
+}
+
+// The size within the margin
+fn measure_box(drawable: &dyn Draw, cx: &LayoutCx, constraints: Constraints) -> Size {
+    let params = drawable.params();
+    let padding = drawable.style().padding;
+
+    let max_width = params.max_width.min(constraints.max.width);
+    let min_width = params.min_width.max(constraints.min.width);
+    let max_height = params.max_height.min(constraints.max.height);
+    let min_height = params.min_height.max(constraints.min.height);
+
+    let known = |length: Length, max: f32| match length {
+        Length::Fixed(length) => Some(length),
+        Length::MatchParent if max.is_finite() => Some(max),
+        Length::MatchParent | Length::WrapContent => None,
+    };
+    let mut width = known(params.width, max_width).map(|w| clamp(w, min_width, max_width));
+    let mut height = known(params.height, max_height).map(|h| clamp(h, min_height, max_height));
+
+    if let Some(ratio) = params.aspect_ratio.filter(|ratio| *ratio > 0.) {
+        match (width, height) {
+            (Some(w), None) => height = Some(w / ratio),
+            (None, Some(h)) => width = Some(h * ratio),
+            _ => {}
+        }
+    }
+
+    let (width, height) = match (width, height) {
+        (Some(width), Some(height)) => (width, height),
+        (width, height) => {
+            let max = Size::new(width.unwrap_or(max_width), height.unwrap_or(max_height));
+            let content = padding.expand_size(drawable.measure(cx, padding.shrink_size(max)));
+            let width = clamp(width.unwrap_or(content.width), min_width, max_width);
+            let height = match (height, params.aspect_ratio) {
+                (Some(height), _) => height,
+                (None, Some(ratio)) if ratio > 0. => width / ratio,
+                (None, _) => content.height,
+            };
+            (width, height)
+        }
+    };
+
+    Size::new(
+        clamp(width, min_width, max_width),
+        clamp(height, min_height, max_height),
+    )
+}
+
+/// Draws a drawable at the place `measure` made for it: its style, then its content within
+/// the padding. It recognizes the gestures the drawable has handlers for.
+pub fn draw(drawable: &dyn Draw, screen: &mut Screen, rect: Rect) -> ScreenHandle {
+    let style = drawable.style();
+    if style.visibility != Visibility::Visible {
+        return ScreenHandle::new(Interaction {
+            enabled: false,
+            ..Interaction::new(rect)
+        });
+    }
+    let outer = style.margin.shrink_rect(rect);
+    let kinds = drawable.gestures().map(GestureHandlers::kinds);
+    let gestures = match kinds {
+        Some(kinds) if !kinds.is_empty() => screen.backend.gestures(outer, &kinds),
+        _ => vec![],
+    };
+
+    let opacity = screen.backend.opacity();
+    screen.backend.set_opacity(opacity * style.opacity);
+    if style.has_box() {
+        screen.backend.paint_box(outer, &style);
+    }
+    let mut handle = drawable.draw(screen, style.padding.shrink_rect(outer));
+    screen.backend.set_opacity(opacity);
+    handle.gestures = gestures;
+    handle
+}
+
+/// Calls the gesture handlers of a drawable with what `draw` recognized, then its `respond`
+pub fn respond(drawable: &dyn Draw, handle: &ScreenHandle) {
+    if let Some(handlers) = drawable.gestures() {
+        handlers.respond(&handle.gestures);
+    }
+    drawable.respond(handle);
+}
+
+/// What tests see of a drawable at the place `measure` made for it. The bounds include the
+/// padding.
+pub fn semantics(drawable: &dyn Draw, cx: &LayoutCx, rect: Rect) -> SemanticNode {
+    let style = drawable.style();
+    if style.visibility != Visibility::Visible {
+        return SemanticNode::new(Role::Custom, rect);
+    }
+    let outer = style.margin.shrink_rect(rect);
+    SemanticNode {
+        bounds: outer,
+        ..drawable.semantics(cx, style.padding.shrink_rect(outer))
+    }
+}
+
+// Unlike f32::clamp this does not panic when min > max. The max wins.
+fn clamp(value: f32, min: f32, max: f32) -> f32 {
+    value.max(min).min(max)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{measure, Constraints, LayoutCx, LayoutParams, Length, Size};
+    use crate::{
+        assets::{ImageSource, ImageStatus},
+        backend::{Backend, ButtonColors, Interaction, Pointer, Sense, TextFieldRun, TextRun},
+        color::Color,
+        drawables::traits::Draw,
+        gestures::{Gesture, GestureKind},
+        layout::Rect,
+        navigation::Route,
+        screen::{Screen, ScreenHandle},
+        style::Style,
+    };
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub(crate) struct TestRoute;
+
+    impl Route for TestRoute {}
+
+    /// A drawable with a fixed content size, for layout tests
+    pub(crate) struct Content(pub LayoutParams, pub Size);
+
+    impl Draw for Content {
+        fn params(&self) -> LayoutParams {
+            self.0
+        }
+
+        fn measure(&self, _cx: &LayoutCx, _max: Size) -> Size {
+            self.1
+        }
+
+        // Draws nothing, like the test backend
+        fn draw(&self, _screen: &mut Screen, rect: Rect) -> ScreenHandle {
+            ScreenHandle::new(Interaction::new(rect))
+        }
+    }
+
+    /// Measures text as 8 by 16 per character, on a single line. Images are 100 by 50 and
+    /// loaded.
+    pub(crate) struct TestBackend;
+
+    impl Backend for TestBackend {
+        fn available_rect(&self) -> Rect {
+            Rect::new(0., 0., 200., 300.)
+        }
+
+        fn item_spacing(&self) -> f32 {
+            0.
+        }
+
+        fn measure_text(&self, text: &TextRun, _max: Size) -> Size {
+            Size::new(8. * text.text.chars().count() as f32, 16.)
+        }
+
+        fn measure_button(&self, label: &str, _max: Size) -> Size {
+            Size::new(8. * label.chars().count() as f32 + 8., 20.)
+        }
+
+        fn interact(&mut self, rect: Rect, _sense: Sense) -> Interaction {
+            Interaction::new(rect)
+        }
+
+        fn pointer(&self) -> Pointer {
+            Pointer::default()
+        }
+
+        fn gestures(&mut self, _rect: Rect, _kinds: &[GestureKind]) -> Vec<Gesture> {
+            vec![]
+        }
+
+        fn fill_rect(&mut self, _rect: Rect, _color: Color) {}
+
+        fn paint_path(&mut self, _points: &[(f32, f32)], _width: f32, _color: Color) {}
+
+        fn spinner(&mut self, _rect: Rect, _color: Color) {}
+
+        fn paint_box(&mut self, _rect: Rect, _style: &Style) {}
+
+        fn set_opacity(&mut self, _opacity: f32) {}
+
+        fn opacity(&self) -> f32 {
+            1.
+        }
+
+        fn set_clip_rect(&mut self, _rect: Rect) {}
+
+        fn clip_rect(&self) -> Rect {
+            self.available_rect()
+        }
+
+        fn image_status(&self, _source: &ImageSource) -> ImageStatus {
+            ImageStatus::Ready(Size::new(100., 50.))
+        }
+
+        fn paint_image(&mut self, _rect: Rect, _source: &ImageSource, _uv: Rect, _tint: Color) {}
+
+        fn text(&mut self, rect: Rect, _text: &TextRun) -> Interaction {
+            Interaction::new(rect)
+        }
+
+        fn measure_text_field(&self, field: &TextFieldRun, max: Size) -> Size {
+            Size::new(max.width.min(160.), 20. * field.rows as f32)
+        }
+
+        fn text_field(
+            &mut self,
+            rect: Rect,
+            field: &TextFieldRun,
+            _text: &mut String,
+        ) -> Interaction {
+            Interaction {
+                enabled: field.enabled,
+                ..Interaction::new(rect)
+            }
+        }
+
+        fn button(
+            &mut self,
+            rect: Rect,
+            _label: &str,
+            _colors: ButtonColors,
+            enabled: bool,
+        ) -> Interaction {
+            Interaction {
+                enabled,
+                ..Interaction::new(rect)
+            }
+        }
+    }
+
+    pub(crate) fn cx() -> LayoutCx<'static> {
+        LayoutCx::new(&TestBackend)
+    }
+
+    /// Runs an egui frame of 300 by 300 with `events`, and draws what `show` queued
+    #[cfg(feature = "egui")]
+    pub(crate) fn egui_frame(
+        ctx: &egui::Context,
+        time: Option<f64>,
+        events: Vec<egui::Event>,
+        show: impl FnOnce(&mut Screen),
+    ) {
+        use crate::{backend::egui::EguiBackend, navigation::Navigator};
+        use egui::{pos2, vec2, CentralPanel, RawInput};
+
+        let input = RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(pos2(0., 0.), vec2(300., 300.))),
+            time,
+            events,
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let mut backend = EguiBackend::new(ui);
+                let mut screen = Screen::new(&mut backend, Navigator::new(TestRoute));
+                show(&mut screen);
+                screen.draw();
+            });
+        });
+    }
+
+    fn params(width: Length, height: Length) -> LayoutParams {
+        LayoutParams {
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn wrap_content_and_match_parent() {
+        let loose = Constraints::loose(Size::new(200., 300.));
+        let content = Size::new(40., 20.);
+
+        let wrap = Content(params(Length::WrapContent, Length::WrapContent), content);
+        assert_eq!(measure(&wrap, &cx(), loose), content);
+
+        let matched = Content(params(Length::MatchParent, Length::Fixed(10.)), content);
+        assert_eq!(measure(&matched, &cx(), loose), Size::new(200., 10.));
+
+        // Without a bounded parent, match parent falls back to the content
+        let unbounded = Constraints::loose(Size::new(200., f32::INFINITY));
+        let matched = Content(params(Length::Fixed(10.), Length::MatchParent), content);
+        assert_eq!(measure(&matched, &cx(), unbounded), Size::new(10., 20.));
+    }
+
+    #[test]
+    fn min_and_max() {
+        let loose = Constraints::loose(Size::new(200., 300.));
+        let mut clamped = params(Length::MatchParent, Length::Fixed(5.));
+        clamped.max_width = 120.;
+        clamped.min_height = 30.;
+        let clamped = Content(clamped, Size::ZERO);
+        assert_eq!(measure(&clamped, &cx(), loose), Size::new(120., 30.));
+
+        // The parent max wins over the own min
+        let mut too_large = params(Length::Fixed(500.), Length::Fixed(5.));
+        too_large.min_width = 400.;
+        let too_large = Content(too_large, Size::ZERO);
+        assert_eq!(measure(&too_large, &cx(), loose).width, 200.);
+    }
+
+    #[test]
+    fn aspect_ratio() {
+        let loose = Constraints::loose(Size::new(200., 300.));
+        let mut wide = params(Length::MatchParent, Length::WrapContent);
+        wide.aspect_ratio = Some(16. / 9.);
+        let wide = Content(wide, Size::new(10., 10.));
+        assert_eq!(measure(&wide, &cx(), loose), Size::new(200., 112.5));
+
+        let mut square = params(Length::WrapContent, Length::Fixed(50.));
+        square.aspect_ratio = Some(1.);
+        let square = Content(square, Size::new(10., 10.));
+        assert_eq!(measure(&square, &cx(), loose), Size::new(50., 50.));
+    }
+}
@@ -1,18 +1,67 @@
+use navigation::Route;
 use screen::Screen;
+use theme::{Appearance, Theme};
 
+pub mod assets;
+pub mod backend;
+pub mod color;
 pub mod drawables;
+pub mod gestures;
+pub mod layout;
+pub mod lifecycle;
+pub mod message;
+pub mod navigation;
+pub mod pages;
 pub mod screen;
+pub mod semantics;
+pub mod store;
 pub mod structures;
+pub mod style;
+pub mod task;
+pub mod theme;
+pub mod timer;
 
 pub type PageFn = Box<dyn Page>;
 
 pub trait RustApp {
-    fn pages(&mut self) -> Vec<PageFn>;
+    type Route: Route;
 
-    fn current_page(&self) -> String;
+    /// The route the app opens on
+    fn home(&self) -> Self::Route;
+
+    /// The routes the viewer shows side by side
+    fn routes(&self) -> Vec<Self::Route>;
+
+    /// Maps a route to its page. Route fields are handed to the page here.
+    ///
+    /// The runner keeps the page while its route is shown or on the back stack.
+    fn page(&mut self, route: &Self::Route) -> PageFn;
+
+    /// Asked at the start of every frame for each page the runner keeps. Returns true to have
+    /// it built again.
+    fn rebuild_page(&mut self, _route: &Self::Route) -> bool {
+        false
+    }
 
     fn start(&mut self);
 
+    /// The look of the pages, for the appearance the user chose
+    fn theme(&self, appearance: Appearance) -> Theme {
+        Theme::for_appearance(appearance)
+    }
+
+    /// Called when the app goes to the background, as when its window loses the focus
+    fn pause(&mut self) {}
+
+    /// Called when the app comes back from the background
+    fn resume(&mut self) {}
+
+    /// Called by the runner at the end of every frame, after the callbacks ran. Returns true
+    /// when the app changed and should be drawn again.
+    fn frame_end(&mut self) -> bool {
+        false
+    }
+
     fn new() -> Self
     where
         Self: Sized;
@@ -21,4 +70,10 @@ pub trait RustApp {
 pub trait Page {
     fn name(&self) -> String;
     fn show(&mut self, screen: &mut Screen);
+
+    /// Called before the page is shown as the current page
+    fn on_appear(&mut self) {}
+
+    /// Called when another page became the current page
+    fn on_disappear(&mut self) {}
 }
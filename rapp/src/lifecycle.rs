@@ -0,0 +1,250 @@
+//! Tells pages when they appear and disappear, and the app when it goes to the background.
+//! Pages that disappear lose the tasks they spawned, unless the app builds the page of the
+//! same route again.
+//!
+//! The runner and the test driver keep a `Lifecycle` next to the navigator and report every
+//! frame to it, and drop pages through it.
+
+use crate::{
+    navigation::{Navigator, Route},
+    pages::PageCache,
+    RustApp,
+};
+
+pub struct Lifecycle<R: Route> {
+    // The route whose page appeared last
+    shown: Option<R>,
+    paused: bool,
+}
+
+impl<R: Route> Lifecycle<R> {
+    pub fn new() -> Self {
+        Self {
+            shown: None,
+            paused: false,
+        }
+    }
+
+    /// Drops the pages of the routes that are not in `keep` and builds those the app wants to
+    /// build again, see `PageCache::retain`. If the page that was shown is one of them, it
+    /// disappears, and the page of the current route appears in the next `navigated`.
+    pub fn retain<A: RustApp<Route = R>>(
+        &mut self,
+        app: &mut A,
+        pages: &mut PageCache<R>,
+        keep: &[R],
+    ) {
+        for (route, mut page) in pages.retain(app, keep) {
+            if self.shown.as_ref() == Some(&route) {
+                page.on_disappear();
+                self.shown = None;
+            }
+        }
+    }
+
+    /// Lets the page that was shown disappear and the current one appear, if the current
+    /// route changed since the last call. The tasks of the page that disappeared are
+    /// cancelled. Returns true if it did.
+    pub fn navigated<A: RustApp<Route = R>>(
+        &mut self,
+        app: &mut A,
+        pages: &mut PageCache<R>,
+        navigator: &Navigator<R>,
+    ) -> bool {
+        let current = navigator.current();
+        if self.shown.as_ref() == Some(&current) {
+            return false;
+        }
+        // A page that was dropped is not built again just to disappear
+        if let Some(shown) = self.shown.take().filter(|shown| pages.contains(shown)) {
+            pages.get(app, &shown).on_disappear();
+            pages.tasks(app, &shown).cancel();
+        }
+        pages.get(app, &current).on_appear();
+        self.shown = Some(current);
+        true
+    }
+
+    /// Pauses or resumes the app when it went to the background or came back. Returns true
+    /// if it did.
+    pub fn set_paused<A: RustApp<Route = R>>(&mut self, app: &mut A, paused: bool) -> bool {
+        if paused == self.paused {
+            return false;
+        }
+        self.paused = paused;
+        if paused {
+            app.pause();
+        } else {
+            app.resume();
+        }
+        true
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+}
+
+impl<R: Route> Default for Lifecycle<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lifecycle;
+    use crate::{
+        navigation::{Navigator, Route},
+        pages::PageCache,
+        screen::Screen,
+        Page, PageFn, RustApp,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum TestRoute {
+        Home,
+        Details,
+    }
+
+    impl Route for TestRoute {}
+
+    type Events = Rc<RefCell<Vec<String>>>;
+
+    #[derive(Default)]
+    struct TestApp {
+        events: Events,
+        built: u32,
+        rebuild: bool,
+    }
+
+    impl RustApp for TestApp {
+        type Route = TestRoute;
+
+        fn home(&self) -> TestRoute {
+            TestRoute::Home
+        }
+
+        fn routes(&self) -> Vec<TestRoute> {
+            vec![TestRoute::Home, TestRoute::Details]
+        }
+
+        fn page(&mut self, route: &TestRoute) -> PageFn {
+            self.built += 1;
+            Box::new(TestPage {
+                route: route.clone(),
+                events: self.events.clone(),
+            })
+        }
+
+        fn start(&mut self) {}
+
+        fn rebuild_page(&mut self, _route: &TestRoute) -> bool {
+            self.rebuild
+        }
+
+        fn pause(&mut self) {
+            self.events.borrow_mut().push("pause".to_string());
+        }
+
+        fn resume(&mut self) {
+            self.events.borrow_mut().push("resume".to_string());
+        }
+
+        fn new() -> Self {
+            Self::default()
+        }
+    }
+
+    struct TestPage {
+        route: TestRoute,
+        events: Events,
+    }
+
+    impl Page for TestPage {
+        fn name(&self) -> String {
+            format!("{:?}", self.route)
+        }
+
+        fn show(&mut self, _screen: &mut Screen) {}
+
+        fn on_appear(&mut self) {
+            let event = format!("{:?} appeared", self.route);
+            self.events.borrow_mut().push(event);
+        }
+
+        fn on_disappear(&mut self) {
+            let event = format!("{:?} disappeared", self.route);
+            self.events.borrow_mut().push(event);
+        }
+    }
+
+    #[test]
+    fn pages_appear_and_the_app_pauses() {
+        let mut app = TestApp::new();
+        let navigator = Navigator::new(app.home());
+        let mut pages = PageCache::new();
+        let mut lifecycle = Lifecycle::new();
+
+        assert!(lifecycle.navigated(&mut app, &mut pages, &navigator));
+        assert!(!lifecycle.navigated(&mut app, &mut pages, &navigator));
+        navigator.push(TestRoute::Details);
+        navigator.apply();
+        assert!(lifecycle.navigated(&mut app, &mut pages, &navigator));
+
+        assert!(lifecycle.set_paused(&mut app, true));
+        assert!(!lifecycle.set_paused(&mut app, true));
+        assert!(lifecycle.paused());
+        lifecycle.set_paused(&mut app, false);
+
+        assert_eq!(
+            *app.events.borrow(),
+            [
+                "Home appeared",
+                "Home disappeared",
+                "Details appeared",
+                "pause",
+                "resume"
+            ]
+        );
+    }
+
+    #[test]
+    fn dropped_pages_disappear_once() {
+        let mut app = TestApp::new();
+        let navigator = Navigator::new(app.home());
+        let mut pages = PageCache::new();
+        let mut lifecycle = Lifecycle::new();
+        lifecycle.navigated(&mut app, &mut pages, &navigator);
+
+        // Built again, so the new page appears
+        app.rebuild = true;
+        lifecycle.retain(&mut app, &mut pages, &[TestRoute::Home]);
+        app.rebuild = false;
+        assert!(lifecycle.navigated(&mut app, &mut pages, &navigator));
+
+        navigator.push(TestRoute::Details);
+        navigator.apply();
+        lifecycle.navigated(&mut app, &mut pages, &navigator);
+        // Dropped before the lifecycle saw the navigation
+        navigator.pop();
+        navigator.apply();
+        lifecycle.retain(&mut app, &mut pages, &navigator.stack());
+        lifecycle.navigated(&mut app, &mut pages, &navigator);
+
+        assert_eq!(
+            *app.events.borrow(),
+            [
+                "Home appeared",
+                "Home disappeared",
+                "Home appeared",
+                "Home disappeared",
+                "Details appeared",
+                "Details disappeared",
+                "Home appeared"
+            ]
+        );
+        assert_eq!(app.built, 3);
+    }
+}
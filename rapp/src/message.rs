@@ -0,0 +1,227 @@
+//! An alternative to `RustApp` in the style of Elm: the app is a state that messages update,
+//! and a view that shows it.
+//!
+//! Drawables post messages to a mailbox instead of calling closures. At the end of every
+//! frame the runner hands them to `MessageApp::update`, in order, and keeps a log of them.
+
+use crate::{navigation::Route, screen::Screen, Page, PageFn, RustApp};
+use std::{
+    any::Any,
+    cell::{Ref, RefCell},
+    fmt::Debug,
+    rc::Rc,
+};
+
+pub trait MessageApp {
+    type Msg: Clone + Debug + 'static;
+
+    fn update(&mut self, msg: Self::Msg);
+
+    /// Shows the state. Changes go through messages.
+    fn view(&self, screen: &mut Screen);
+
+    fn new() -> Self
+    where
+        Self: Sized;
+}
+
+/// Where drawables post messages. Clones refer to the same mailbox.
+pub struct Mailbox<M>(Rc<RefCell<Vec<M>>>);
+
+impl<M> Mailbox<M> {
+    pub fn post(&self, msg: M) {
+        self.0.borrow_mut().push(msg);
+    }
+
+    pub(crate) fn take(&self) -> Vec<M> {
+        std::mem::take(&mut self.0.borrow_mut())
+    }
+}
+
+impl<M: 'static> Mailbox<M> {
+    /// Panics when it is not a mailbox for `M`, as when the app is not a `MessageApp`
+    pub(crate) fn downcast(mailbox: Option<&Rc<dyn Any>>) -> Self {
+        mailbox
+            .and_then(|mailbox| mailbox.downcast_ref::<Self>())
+            .cloned()
+            .unwrap_or_else(|| {
+                panic!(
+                    "the app is not a MessageApp with messages of type {}",
+                    std::any::type_name::<M>()
+                )
+            })
+    }
+}
+
+impl<M> Clone for Mailbox<M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<M> Default for Mailbox<M> {
+    fn default() -> Self {
+        Self(Rc::new(RefCell::new(vec![])))
+    }
+}
+
+/// The only route of a `Program`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViewRoute;
+
+impl Route for ViewRoute {}
+
+/// Runs a `MessageApp` as a `RustApp`, so the runner and the test driver can run it
+pub struct Program<A: MessageApp> {
+    app: Rc<RefCell<A>>,
+    mailbox: Mailbox<A::Msg>,
+    log: Vec<A::Msg>,
+}
+
+impl<A: MessageApp> Program<A> {
+    pub fn from_app(app: A) -> Self {
+        Self {
+            app: Rc::new(RefCell::new(app)),
+            mailbox: Mailbox::default(),
+            log: vec![],
+        }
+    }
+
+    pub fn state(&self) -> Ref<'_, A> {
+        self.app.borrow()
+    }
+
+    /// Every message that was handled, oldest first
+    pub fn log(&self) -> &[A::Msg] {
+        &self.log
+    }
+
+    /// Posts a message as a drawable would. It is handled at the end of the frame.
+    pub fn post(&self, msg: A::Msg) {
+        self.mailbox.post(msg);
+    }
+}
+
+impl<A: MessageApp + 'static> RustApp for Program<A> {
+    type Route = ViewRoute;
+
+    fn home(&self) -> ViewRoute {
+        ViewRoute
+    }
+
+    fn routes(&self) -> Vec<ViewRoute> {
+        vec![ViewRoute]
+    }
+
+    fn page(&mut self, _route: &ViewRoute) -> PageFn {
+        Box::new(ViewPage {
+            app: self.app.clone(),
+            mailbox: self.mailbox.clone(),
+        })
+    }
+
+    fn start(&mut self) {}
+
+    fn frame_end(&mut self) -> bool {
+        let messages = self.mailbox.take();
+        let changed = !messages.is_empty();
+        for msg in messages {
+            self.app.borrow_mut().update(msg.clone());
+            self.log.push(msg);
+        }
+        changed
+    }
+
+    fn new() -> Self {
+        Self::from_app(A::new())
+    }
+}
+
+struct ViewPage<A: MessageApp> {
+    app: Rc<RefCell<A>>,
+    mailbox: Mailbox<A::Msg>,
+}
+
+impl<A: MessageApp + 'static> Page for ViewPage<A> {
+    fn name(&self) -> String {
+        std::any::type_name::<A>().to_string()
+    }
+
+    fn show(&mut self, screen: &mut Screen) {
+        screen.context.mailbox = Some(Rc::new(self.mailbox.clone()));
+        self.app.borrow().view(screen);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MessageApp, Program, ViewRoute};
+    use crate::{
+        backend::Interaction,
+        layout::{tests::TestBackend, Rect},
+        navigation::Navigator,
+        screen::{Screen, ScreenHandle},
+        RustApp,
+    };
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Msg {
+        Add(u32),
+        Reset,
+    }
+
+    struct Counter(u32);
+
+    impl MessageApp for Counter {
+        type Msg = Msg;
+
+        fn update(&mut self, msg: Msg) {
+            match msg {
+                Msg::Add(amount) => self.0 += amount,
+                Msg::Reset => self.0 = 0,
+            }
+        }
+
+        fn view(&self, screen: &mut Screen) {
+            screen.text(format!("Count {}", self.0));
+            screen.button("Add").message(Msg::Add(2));
+            screen.button("Reset").message(Msg::Reset);
+        }
+
+        fn new() -> Self {
+            Counter(0)
+        }
+    }
+
+    #[test]
+    fn buttons_post_messages() {
+        let mut program = Program::<Counter>::new();
+        let mut page = program.page(&ViewRoute);
+
+        let mut backend = TestBackend;
+        let mut screen = Screen::new(&mut backend, Navigator::new(ViewRoute));
+        page.show(&mut screen);
+        let queue = screen.queue.borrow().clone();
+        let click = ScreenHandle::new(Interaction {
+            clicked: true,
+            ..Interaction::new(Rect::default())
+        });
+        queue[1].borrow().respond(&click);
+        queue[1].borrow().respond(&click);
+        assert_eq!(program.state().0, 0);
+
+        // Handled at the end of the frame
+        assert!(program.frame_end());
+        assert_eq!(program.state().0, 4);
+        queue[2].borrow().respond(&click);
+        program.post(Msg::Add(1));
+        assert!(program.frame_end());
+        assert!(!program.frame_end());
+
+        assert_eq!(program.state().0, 1);
+        assert_eq!(
+            program.log(),
+            [Msg::Add(2), Msg::Add(2), Msg::Reset, Msg::Add(1)]
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
+
+/// A place in the app. Usually an enum defined by the app, with the page arguments as fields:
+///
+/// ```
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum AppRoute {
+///     Home,
+///     Details { id: u64 },
+/// }
+///
+/// impl rapp::navigation::Route for AppRoute {}
+/// ```
+pub trait Route: Clone + PartialEq + Debug + 'static {}
+
+/// Handle to the page stack of an app.
+///
+/// Pages get it through [`Screen::navigator`](crate::screen::Screen::navigator). Requests are
+/// queued and only applied by the runner after the frame is drawn, so every page in a frame
+/// sees the same current page.
+pub struct Navigator<R: Route>(Rc<RefCell<NavigatorData<R>>>);
+
+struct NavigatorData<R> {
+    stack: Vec<R>,
+    pending: Vec<NavRequest<R>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum NavRequest<R> {
+    Push(R),
+    Pop,
+    Replace(R),
+    Reset(R),
+}
+
+// Derive would require R: Clone for the handle itself
+impl<R: Route> Clone for Navigator<R> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<R: Route> Navigator<R> {
+    pub fn new(home: R) -> Self {
+        Self(Rc::new(RefCell::new(NavigatorData {
+            stack: vec![home],
+            pending: vec![],
+        })))
+    }
+
+    /// The route on top of the stack
+    pub fn current(&self) -> R {
+        let data = self.0.borrow();
+        data.stack
+            .last()
+            .cloned()
+            .expect("the navigation stack always holds a root")
+    }
+
+    /// The back stack, from the root route up to the current route
+    pub fn stack(&self) -> Vec<R> {
+        self.0.borrow().stack.clone()
+    }
+
+    pub fn can_pop(&self) -> bool {
+        self.0.borrow().stack.len() > 1
+    }
+
+    /// Go to `route` and keep the current route on the back stack
+    pub fn push(&self, route: R) {
+        self.request(NavRequest::Push(route));
+    }
+
+    /// Go back to the previous route. The root route is never popped.
+    pub fn pop(&self) {
+        self.request(NavRequest::Pop);
+    }
+
+    /// Swap the current route for `route` without growing the back stack
+    pub fn replace(&self, route: R) {
+        self.request(NavRequest::Replace(route));
+    }
+
+    /// Clear the back stack and make `route` the root
+    pub fn reset(&self, route: R) {
+        self.request(NavRequest::Reset(route));
+    }
+
+    /// Applies the queued requests in order. Returns true if the current route changed.
+    pub fn apply(&self) -> bool {
+        let mut data = self.0.borrow_mut();
+        let before = data.stack.last().cloned();
+        let pending = std::mem::take(&mut data.pending);
+        for request in pending {
+            match request {
+                NavRequest::Push(route) => data.stack.push(route),
+                NavRequest::Pop => {
+                    if data.stack.len() > 1 {
+                        data.stack.pop();
+                    }
+                }
+                NavRequest::Replace(route) => {
+                    data.stack.pop();
+                    data.stack.push(route);
+                }
+                NavRequest::Reset(route) => {
+                    data.stack.clear();
+                    data.stack.push(route);
+                }
+            }
+        }
+        data.stack.last().cloned() != before
+    }
+
+    fn request(&self, request: NavRequest<R>) {
+        self.0.borrow_mut().pending.push(request);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Navigator, Route};
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum TestRoute {
+        Home,
+        List,
+        Details { id: u64 },
+        Login,
+    }
+
+    impl Route for TestRoute {}
+
+    #[test]
+    fn requests_are_applied_later() {
+        let nav = Navigator::new(TestRoute::Home);
+        nav.push(TestRoute::Details { id: 7 });
+        assert_eq!(nav.current(), TestRoute::Home);
+        assert!(nav.apply());
+        assert_eq!(nav.current(), TestRoute::Details { id: 7 });
+        assert!(!nav.apply());
+    }
+
+    #[test]
+    fn push_pop_replace_reset() {
+        use TestRoute::*;
+
+        let nav = Navigator::new(Home);
+        nav.push(List);
+        nav.push(Details { id: 1 });
+        nav.apply();
+        assert_eq!(nav.stack(), vec![Home, List, Details { id: 1 }]);
+
+        nav.replace(Details { id: 2 });
+        nav.apply();
+        assert_eq!(nav.stack(), vec![Home, List, Details { id: 2 }]);
+
+        nav.pop();
+        nav.apply();
+        assert_eq!(nav.current(), List);
+
+        nav.reset(Login);
+        nav.apply();
+        assert_eq!(nav.stack(), vec![Login]);
+        assert!(!nav.can_pop());
+    }
+
+    #[test]
+    fn root_is_never_popped() {
+        let nav = Navigator::new(TestRoute::Home);
+        nav.pop();
+        nav.pop();
+        assert!(!nav.apply());
+        assert_eq!(nav.current(), TestRoute::Home);
+    }
+}
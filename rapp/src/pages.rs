@@ -0,0 +1,176 @@
+//! Keeps the pages of an app between frames, so they can hold state in their fields.
+
+use crate::{navigation::Route, task::TaskScope, Page, PageFn, RustApp};
+
+/// The pages the runner built, by route. A page is built the first time its route is shown
+/// and kept while the route is shown or on the back stack.
+pub struct PageCache<R: Route> {
+    pages: Vec<(R, PageFn, TaskScope)>,
+}
+
+impl<R: Route> PageCache<R> {
+    pub fn new() -> Self {
+        Self { pages: vec![] }
+    }
+
+    /// Drops the pages of the routes that are not in `keep`, with their tasks, and builds
+    /// those the app wants to build again. A page that is built again keeps the tasks of the
+    /// one it replaces. Returns the pages that were dropped or replaced, see
+    /// `Lifecycle::retain`.
+    pub fn retain<A: RustApp<Route = R>>(&mut self, app: &mut A, keep: &[R]) -> Vec<(R, PageFn)> {
+        let mut dropped = vec![];
+        for (route, page, tasks) in std::mem::take(&mut self.pages) {
+            if !keep.contains(&route) {
+                tasks.cancel();
+                dropped.push((route, page));
+            } else if app.rebuild_page(&route) {
+                let rebuilt = app.page(&route);
+                self.pages.push((route.clone(), rebuilt, tasks));
+                dropped.push((route, page));
+            } else {
+                self.pages.push((route, page, tasks));
+            }
+        }
+        dropped
+    }
+
+    /// Whether the page of the route was built and is kept
+    pub fn contains(&self, route: &R) -> bool {
+        self.pages.iter().any(|(cached, ..)| cached == route)
+    }
+
+    /// The page of the route, built by the app if there is none yet
+    pub fn get<A: RustApp<Route = R>>(&mut self, app: &mut A, route: &R) -> &mut dyn Page {
+        let index = self.index(app, route);
+        &mut *self.pages[index].1
+    }
+
+    /// The tasks of the page of the route. The page is shown and its callbacks run within
+    /// the scope, so the tasks they spawn are cancelled when it disappears.
+    pub fn tasks<A: RustApp<Route = R>>(&mut self, app: &mut A, route: &R) -> TaskScope {
+        let index = self.index(app, route);
+        self.pages[index].2.clone()
+    }
+
+    fn index<A: RustApp<Route = R>>(&mut self, app: &mut A, route: &R) -> usize {
+        match self.pages.iter().position(|(cached, ..)| cached == route) {
+            Some(index) => index,
+            None => {
+                let page = app.page(route);
+                self.pages.push((route.clone(), page, TaskScope::new()));
+                self.pages.len() - 1
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+}
+
+impl<R: Route> Default for PageCache<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PageCache;
+    use crate::{
+        layout::tests::TestBackend,
+        navigation::{Navigator, Route},
+        screen::Screen,
+        Page, PageFn, RustApp,
+    };
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum TestRoute {
+        Home,
+        Details { id: u64 },
+    }
+
+    impl Route for TestRoute {}
+
+    #[derive(Default)]
+    struct TestApp {
+        built: u32,
+        rebuild: bool,
+    }
+
+    impl RustApp for TestApp {
+        type Route = TestRoute;
+
+        fn home(&self) -> TestRoute {
+            TestRoute::Home
+        }
+
+        fn routes(&self) -> Vec<TestRoute> {
+            vec![TestRoute::Home]
+        }
+
+        fn page(&mut self, _route: &TestRoute) -> PageFn {
+            self.built += 1;
+            Box::new(CountingPage(0))
+        }
+
+        fn start(&mut self) {}
+
+        fn rebuild_page(&mut self, route: &TestRoute) -> bool {
+            self.rebuild && *route == TestRoute::Home
+        }
+
+        fn new() -> Self {
+            Self::default()
+        }
+    }
+
+    // Counts how often it was shown
+    struct CountingPage(u32);
+
+    impl Page for CountingPage {
+        fn name(&self) -> String {
+            format!("Shown {} times", self.0)
+        }
+
+        fn show(&mut self, _screen: &mut Screen) {
+            self.0 += 1;
+        }
+    }
+
+    #[test]
+    fn pages_keep_their_state() {
+        let mut app = TestApp::new();
+        let mut pages = PageCache::new();
+        let details = TestRoute::Details { id: 1 };
+        let keep = [TestRoute::Home, details.clone()];
+        let mut backend = TestBackend;
+
+        for _ in 0..3 {
+            pages.retain(&mut app, &keep);
+            for route in &keep {
+                let mut screen = Screen::new(&mut backend, Navigator::new(route.clone()));
+                pages.get(&mut app, route).show(&mut screen);
+            }
+        }
+        assert_eq!(app.built, 2);
+        assert_eq!(pages.get(&mut app, &details).name(), "Shown 3 times");
+
+        // Left routes are dropped, and the app decides when to build a page again
+        app.rebuild = true;
+        let dropped = pages.retain(&mut app, &[TestRoute::Home]);
+        let routes: Vec<_> = dropped.iter().map(|(route, _)| route.clone()).collect();
+        assert_eq!(routes, [TestRoute::Home, details.clone()]);
+        assert!(!pages.contains(&details));
+        app.rebuild = false;
+        assert_eq!(
+            pages.get(&mut app, &TestRoute::Home).name(),
+            "Shown 0 times"
+        );
+        assert_eq!(app.built, 3);
+    }
+}
@@ -1,37 +1,165 @@
-use crate::drawables::{placeholder::PlaceHolder, separator::Separator, traits::Draw};
-use egui::{Response, Ui};
-use std::{cell::RefCell, rc::Rc, sync::Arc};
+use crate::{
+    assets::ImageSource,
+    backend::{Backend, Interaction},
+    drawables::{
+        button::Button,
+        image::Image,
+        placeholder::PlaceHolder,
+        radio::RadioGroup,
+        separator::Separator,
+        slider::Slider,
+        text::Text,
+        text_field::TextField,
+        toggle::{Toggle, ToggleKind},
+        traits::Draw,
+    },
+    gestures::Gesture,
+    layout::{measure, Constraints, LayoutCx, Length, Rect},
+    message::Mailbox,
+    navigation::{Navigator, Route},
+    semantics::SemanticTree,
+    store::Store,
+    structures::{
+        column::Column,
+        linear::{Axis, LinearData},
+        list::{List, ListState},
+        row::Row,
+        stack::Stack,
+    },
+    theme::Theme,
+};
+use std::{any::Any, cell::RefCell, ops::RangeInclusive, rc::Rc};
 
 pub struct Screen<'a> {
-    pub egui_ui: &'a mut Ui,
+    pub(crate) backend: &'a mut dyn Backend,
     pub queue: RefCell<Vec<Rc<RefCell<dyn Draw>>>>,
+    pub(crate) context: ScreenContext,
 }
 
-impl Screen<'_> {
-    pub fn draw(&mut self) {
-        let mut other = vec![];
-        {
-            let len = self.queue.borrow().len();
-            let mut a = self.queue.borrow_mut();
-            let bs = a.drain(0..len);
-            for x in bs {
-                other.push(x);
-            }
-        }
-        for b in other {
-            b.borrow().draw(self);
+/// What a screen shares with the screens nested in it
+#[derive(Clone)]
+pub(crate) struct ScreenContext {
+    // A Navigator<R> for the Route of the app
+    pub navigator: Rc<dyn Any>,
+    // A Mailbox<M> for the messages of a MessageApp
+    pub mailbox: Option<Rc<dyn Any>>,
+    // What drawables queued on the screen look like
+    pub theme: Rc<Theme>,
+}
+
+impl ScreenContext {
+    pub fn screen<'b>(&self, backend: &'b mut dyn Backend) -> Screen<'b> {
+        Screen {
+            backend,
+            queue: RefCell::new(vec![]),
+            context: self.clone(),
         }
     }
 }
 
-pub struct ScreenWithArc<'a> {
-    pub arc_ui: Arc<&'a mut Ui>,
+impl<'a> Screen<'a> {
+    pub fn new<R: Route>(backend: &'a mut dyn Backend, navigator: Navigator<R>) -> Self {
+        let context = ScreenContext {
+            navigator: Rc::new(navigator),
+            mailbox: None,
+            theme: Rc::default(),
+        };
+        context.screen(backend)
+    }
+
+    /// Where drawables post the messages of a `MessageApp`. It can be moved into callbacks.
+    ///
+    /// Panics if the app is not a `MessageApp` with messages of type `M`.
+    pub fn mailbox<M: 'static>(&self) -> Mailbox<M> {
+        Mailbox::downcast(self.context.mailbox.as_ref())
+    }
+
+    /// The theme of the drawables queued from now on
+    pub fn theme(&self) -> Rc<Theme> {
+        self.context.theme.clone()
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.context.theme = Rc::new(theme);
+    }
+
+    /// Runs `add_children` with another theme for what it queues
+    pub fn with_theme(&mut self, theme: Theme, add_children: impl FnOnce(&mut Screen)) {
+        let outer = std::mem::replace(&mut self.context.theme, Rc::new(theme));
+        add_children(self);
+        self.context.theme = outer;
+    }
+
+    /// What the drawables render against
+    pub fn backend(&mut self) -> &mut dyn Backend {
+        self.backend
+    }
+
+    /// Handle to switch pages. It can be moved into callbacks.
+    ///
+    /// Panics if `R` is not the Route type of the app.
+    pub fn navigator<R: Route>(&self) -> Navigator<R> {
+        self.context
+            .navigator
+            .downcast_ref::<Navigator<R>>()
+            .cloned()
+            .unwrap_or_else(|| {
+                panic!(
+                    "the app does not navigate with routes of type {}",
+                    std::any::type_name::<R>()
+                )
+            })
+    }
+
+    /// Lays out what the page queued as a column filling the width of the screen, then draws it
+    pub fn draw(&mut self) {
+        let children = self.queue.borrow_mut().drain(..).collect();
+        let (root, rect) = self.root(children);
+
+        let available = self.backend.available_rect();
+        self.backend
+            .fill_rect(available, self.context.theme.colors.background);
+        let handle = root.draw(self, rect);
+        // Callbacks run once everything is drawn
+        root.respond(&handle);
+    }
+
+    /// What the queued drawables look like to tests, placed where `draw` would place them
+    pub fn semantics(&self) -> SemanticTree {
+        let (root, rect) = self.root(self.queue.borrow().clone());
+        SemanticTree {
+            root: root.semantics(&LayoutCx::new(self.backend), rect),
+        }
+    }
+
+    // The column that holds the drawables of the page, and where it goes
+    fn root(&self, children: Vec<Rc<RefCell<dyn Draw>>>) -> (LinearData, Rect) {
+        let mut root = LinearData::new(Axis::Vertical, children);
+        root.spacing = self.backend.item_spacing();
+        root.layout.width = Length::MatchParent;
+
+        let available = self.backend.available_rect();
+        let cx = LayoutCx::new(self.backend);
+        let size = measure(&root, &cx, Constraints::loose(available.size()));
+        (root, Rect::from_size(available.x, available.y, size))
+    }
+
+    /// Runs `add_children` on a nested screen and returns what it queued
+    pub(crate) fn children(
+        &mut self,
+        add_children: impl FnOnce(&mut Screen),
+    ) -> Vec<Rc<RefCell<dyn Draw>>> {
+        let mut screen = self.context.screen(self.backend);
+        add_children(&mut screen);
+        screen.queue.into_inner()
+    }
 }
 
 // Separator
 impl Screen<'_> {
     pub fn separator(&mut self) -> Separator {
         let default = Separator::default();
+        default.0.borrow_mut().theme = self.theme();
         self.queue.borrow_mut().push(default.0.clone());
         default
     }
@@ -41,11 +169,206 @@ impl Screen<'_> {
 impl Screen<'_> {
     pub fn placeholder(&mut self) -> PlaceHolder {
         let default = PlaceHolder::default();
+        default.0.borrow_mut().theme = self.theme();
+        self.queue.borrow_mut().push(default.0.clone());
+        default
+    }
+}
+
+// Text
+impl Screen<'_> {
+    pub fn text(&mut self, text: impl Into<String>) -> Text {
+        let default = Text::new(text.into());
+        default.0.borrow_mut().theme = self.theme();
+        self.queue.borrow_mut().push(default.0.clone());
+        default
+    }
+}
+
+// Text field
+impl Screen<'_> {
+    /// Edits the text in `value`
+    pub fn text_field(&mut self, value: &Store<String>) -> TextField {
+        let default = TextField::new(value.clone());
+        default.0.borrow_mut().theme = self.theme();
+        self.queue.borrow_mut().push(default.0.clone());
+        default
+    }
+}
+
+// Toggle
+impl Screen<'_> {
+    /// A switch that flips `value`
+    pub fn toggle(&mut self, value: &Store<bool>, label: impl Into<String>) -> Toggle {
+        self.push_toggle(ToggleKind::Switch, value, label.into())
+    }
+
+    /// A checkbox that flips `value`
+    pub fn checkbox(&mut self, value: &Store<bool>, label: impl Into<String>) -> Toggle {
+        self.push_toggle(ToggleKind::Checkbox, value, label.into())
+    }
+
+    fn push_toggle(&mut self, kind: ToggleKind, value: &Store<bool>, label: String) -> Toggle {
+        let default = Toggle::new(kind, value.clone(), label);
+        default.0.borrow_mut().theme = self.theme();
+        self.queue.borrow_mut().push(default.0.clone());
+        default
+    }
+}
+
+// Radio group
+impl Screen<'_> {
+    /// Selects one of the `options` into `value`, each shown with its label
+    pub fn radio_group<T: Clone + PartialEq + 'static>(
+        &mut self,
+        value: &Store<T>,
+        options: impl IntoIterator<Item = (T, impl Into<String>)>,
+    ) -> RadioGroup<T> {
+        let options = options
+            .into_iter()
+            .map(|(option, label)| (option, label.into()))
+            .collect();
+        let default = RadioGroup::new(value.clone(), options);
+        default.0.borrow_mut().theme = self.theme();
+        self.queue.borrow_mut().push(default.0.clone());
+        default
+    }
+}
+
+// Slider
+impl Screen<'_> {
+    /// Picks `value` from `range`
+    pub fn slider(&mut self, value: &Store<f32>, range: RangeInclusive<f32>) -> Slider {
+        let default = Slider::new(value.clone(), range);
+        default.0.borrow_mut().theme = self.theme();
+        self.queue.borrow_mut().push(default.0.clone());
+        default
+    }
+}
+
+// Button
+impl Screen<'_> {
+    pub fn button(&mut self, label: impl Into<String>) -> Button {
+        let default = Button::new(label.into(), self.context.mailbox.clone());
+        default.0.borrow_mut().theme = self.theme();
+        self.queue.borrow_mut().push(default.0.clone());
+        default
+    }
+}
+
+// Image
+impl Screen<'_> {
+    /// An image from the assets of the app, by its path or embedded with `include_asset!`
+    pub fn image(&mut self, source: impl Into<ImageSource>) -> Image {
+        let default = Image::new(source.into());
+        default.0.borrow_mut().theme = self.theme();
         self.queue.borrow_mut().push(default.0.clone());
         default
     }
 }
 
+// Column
+impl Screen<'_> {
+    pub fn column(&mut self, add_children: impl FnOnce(&mut Screen)) -> Column {
+        let column = Column::new(self.children(add_children));
+        self.queue.borrow_mut().push(column.0.clone());
+        column
+    }
+}
+
+// Row
+impl Screen<'_> {
+    pub fn row(&mut self, add_children: impl FnOnce(&mut Screen)) -> Row {
+        let row = Row::new(self.children(add_children));
+        self.queue.borrow_mut().push(row.0.clone());
+        row
+    }
+}
+
+// Stack
+impl Screen<'_> {
+    pub fn stack(&mut self, add_children: impl FnOnce(&mut Screen)) -> Stack {
+        let stack = Stack::new(self.children(add_children));
+        self.queue.borrow_mut().push(stack.0.clone());
+        stack
+    }
+}
+
+// List
+impl Screen<'_> {
+    /// `count` rows that scroll, scrolled as `state` keeps. `build_row` queues the drawables
+    /// of the row at an index, only for the rows in view.
+    pub fn list(
+        &mut self,
+        state: &ListState,
+        count: usize,
+        build_row: impl Fn(&mut Screen, usize) + 'static,
+    ) -> List {
+        let list = List::new(self.context.clone(), state.clone(), count, build_row);
+        self.queue.borrow_mut().push(list.0.clone());
+        list
+    }
+}
+
+/// What happened to a drawable in the frame it was drawn
 pub struct ScreenHandle {
-    pub(crate) _egui_response: Response,
+    pub(crate) interaction: Interaction,
+    // Set by the layout
+    pub(crate) gestures: Vec<Gesture>,
+}
+
+impl ScreenHandle {
+    pub fn new(interaction: Interaction) -> Self {
+        Self {
+            interaction,
+            gestures: vec![],
+        }
+    }
+
+    pub fn rect(&self) -> Rect {
+        self.interaction.rect
+    }
+
+    pub fn clicked(&self) -> bool {
+        self.interaction.clicked
+    }
+
+    pub fn long_pressed(&self) -> bool {
+        self.interaction.long_pressed
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.interaction.enabled
+    }
+
+    pub fn hovered(&self) -> bool {
+        self.interaction.hovered
+    }
+
+    pub fn changed(&self) -> bool {
+        self.interaction.changed
+    }
+
+    pub fn submitted(&self) -> bool {
+        self.interaction.submitted
+    }
+
+    pub fn focused(&self) -> bool {
+        self.interaction.focused
+    }
+
+    /// Where the pointer is while it presses or drags the drawable
+    pub fn pointer(&self) -> Option<(f32, f32)> {
+        self.interaction.pointer
+    }
+
+    /// How far it was scrolled in this frame. Negative `y` moves the content up.
+    pub fn scroll(&self) -> (f32, f32) {
+        self.interaction.scroll
+    }
+
+    /// What it recognized in this frame, of the kinds it has gesture handlers for
+    pub fn gestures(&self) -> &[Gesture] {
+        &self.gestures
+    }
 }
@@ -0,0 +1,141 @@
+//! What a page shows, as a tree that tests can query without pixels
+
+use crate::layout::Rect;
+
+/// The kind of drawable
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Text,
+    TextField,
+    Button,
+    Placeholder,
+    Separator,
+    Image,
+    Switch,
+    Checkbox,
+    RadioGroup,
+    RadioButton,
+    Slider,
+    /// Shows that something is loading
+    Spinner,
+    Column,
+    Row,
+    Stack,
+    /// Only has the rows that are in view
+    List,
+    /// A drawable from outside of rapp
+    Custom,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SemanticNode {
+    pub role: Role,
+    /// The text it shows, like the label of a button
+    pub label: Option<String>,
+    /// What was entered, like the text of a text field
+    pub value: Option<String>,
+    /// Whether a switch, checkbox or radio button is on
+    pub checked: Option<bool>,
+    /// Where the layout places it
+    pub bounds: Rect,
+    pub enabled: bool,
+    pub test_id: Option<String>,
+    pub children: Vec<SemanticNode>,
+}
+
+impl SemanticNode {
+    /// An enabled node without label, test id or children
+    pub fn new(role: Role, bounds: Rect) -> Self {
+        Self {
+            role,
+            label: None,
+            value: None,
+            checked: None,
+            bounds,
+            enabled: true,
+            test_id: None,
+            children: vec![],
+        }
+    }
+
+    /// This node and all nodes below it, depth first
+    pub fn iter(&self) -> impl Iterator<Item = &SemanticNode> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+}
+
+/// The semantic nodes of a screen, see `Screen::semantics`
+#[derive(Clone, Debug, PartialEq)]
+pub struct SemanticTree {
+    /// The column in which the screen places what the page queued
+    pub root: SemanticNode,
+}
+
+impl SemanticTree {
+    pub fn iter(&self) -> impl Iterator<Item = &SemanticNode> {
+        self.root.iter()
+    }
+
+    /// The first node that shows exactly `text`
+    pub fn find_by_text(&self, text: &str) -> Option<&SemanticNode> {
+        self.iter().find(|node| node.label.as_deref() == Some(text))
+    }
+
+    /// All nodes that show exactly `text`
+    pub fn find_all_by_text(&self, text: &str) -> Vec<&SemanticNode> {
+        self.iter()
+            .filter(|node| node.label.as_deref() == Some(text))
+            .collect()
+    }
+
+    pub fn find_by_test_id(&self, test_id: &str) -> Option<&SemanticNode> {
+        self.iter()
+            .find(|node| node.test_id.as_deref() == Some(test_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Role;
+    use crate::{
+        drawables::traits::{Sizing, TestId},
+        layout::{
+            tests::{TestBackend, TestRoute},
+            Rect,
+        },
+        navigation::Navigator,
+        screen::Screen,
+    };
+
+    #[test]
+    fn find_what_the_page_queued() {
+        let mut backend = TestBackend;
+        let mut screen = Screen::new(&mut backend, Navigator::new(TestRoute));
+        screen.text("Title");
+        screen.row(|screen| {
+            screen.placeholder().width(40).height(10);
+            screen.button("Save").enabled(false).test_id("save");
+        });
+
+        let tree = screen.semantics();
+        assert_eq!(tree.root.role, Role::Column);
+
+        let title = tree.find_by_text("Title").unwrap();
+        assert_eq!(title.role, Role::Text);
+        assert_eq!(title.bounds, Rect::new(0., 0., 40., 16.));
+
+        let save = tree.find_by_test_id("save").unwrap();
+        assert_eq!(save.role, Role::Button);
+        assert_eq!(save.label.as_deref(), Some("Save"));
+        assert!(!save.enabled);
+        assert_eq!(save.bounds, Rect::new(40., 16., 40., 20.));
+
+        assert!(tree.find_by_text("Cancel").is_none());
+        assert_eq!(tree.iter().count(), 5);
+    }
+}
@@ -0,0 +1,114 @@
+//! State that pages share, like a counter, a cart or a login session.
+//!
+//! The app owns its stores and hands clones to the pages it builds. Pages read them in
+//! `Page::show` and change them from callbacks. The runner draws again when any store changed.
+
+use std::{
+    cell::{Cell, Ref, RefCell},
+    fmt,
+    rc::Rc,
+};
+
+thread_local! {
+    static REVISION: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Counts the changes to all stores of this thread. The runner compares it between frames.
+pub fn revision() -> u64 {
+    REVISION.with(|revision| revision.get())
+}
+
+fn changed() {
+    REVISION.with(|revision| revision.set(revision.get() + 1));
+}
+
+/// A shared value. Clones refer to the same value.
+pub struct Store<T>(Rc<StoreData<T>>);
+
+struct StoreData<T> {
+    value: RefCell<T>,
+    revision: Cell<u64>,
+}
+
+impl<T> Store<T> {
+    pub fn new(value: T) -> Self {
+        Self(Rc::new(StoreData {
+            value: RefCell::new(value),
+            revision: Cell::new(0),
+        }))
+    }
+
+    /// Panics when the value is being changed, as in a callback of `update`
+    pub fn read(&self) -> Ref<'_, T> {
+        self.0.value.borrow()
+    }
+
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.read().clone()
+    }
+
+    pub fn set(&self, value: T) {
+        self.update(|current| *current = value);
+    }
+
+    pub fn update<R>(&self, update: impl FnOnce(&mut T) -> R) -> R {
+        let result = update(&mut self.0.value.borrow_mut());
+        self.0.revision.set(self.0.revision.get() + 1);
+        changed();
+        result
+    }
+
+    /// Counts the changes to this store
+    pub fn revision(&self) -> u64 {
+        self.0.revision.get()
+    }
+
+    /// Whether both refer to the same value
+    pub fn ptr_eq(&self, other: &Store<T>) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T> Clone for Store<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Default> Default for Store<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Store<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Store").field(&*self.read()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{revision, Store};
+
+    #[test]
+    fn clones_share_the_value() {
+        let cart = Store::new(vec!["apple"]);
+        let page = cart.clone();
+        let before = revision();
+
+        page.update(|items| items.push("pear"));
+        assert_eq!(cart.get(), vec!["apple", "pear"]);
+        assert_eq!(cart.revision(), 1);
+        assert_eq!(revision(), before + 1);
+
+        cart.set(vec![]);
+        assert!(page.read().is_empty());
+        assert_eq!(page.revision(), 2);
+        assert!(cart.ptr_eq(&page));
+        assert!(!cart.ptr_eq(&Store::default()));
+    }
+}
@@ -1 +1,36 @@
+use super::{
+    linear::{Axis, LinearData},
+    CrossAlign, MainAlign,
+};
+use crate::drawables::traits::{common_modifiers, Draw};
+use std::{cell::RefCell, rc::Rc};
 
+/// Places its children below each other
+#[derive(Clone)]
+pub struct Column(pub(crate) Rc<RefCell<LinearData>>);
+
+impl Column {
+    pub(crate) fn new(children: Vec<Rc<RefCell<dyn Draw>>>) -> Self {
+        Self(Rc::new(RefCell::new(LinearData::new(
+            Axis::Vertical,
+            children,
+        ))))
+    }
+
+    pub fn spacing(self, spacing: u16) -> Self {
+        self.0.borrow_mut().spacing = spacing.into();
+        self
+    }
+    /// Vertical alignment of the children
+    pub fn main_align(self, align: MainAlign) -> Self {
+        self.0.borrow_mut().main_align = align;
+        self
+    }
+    /// Horizontal alignment of the children
+    pub fn cross_align(self, align: CrossAlign) -> Self {
+        self.0.borrow_mut().cross_align = align;
+        self
+    }
+}
+
+common_modifiers!(Column);
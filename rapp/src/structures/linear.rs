@@ -0,0 +1,368 @@
+use super::{CrossAlign, MainAlign};
+use crate::{
+    backend::Sense,
+    drawables::traits::Draw,
+    gestures::GestureHandlers,
+    layout::{self, measure, Constraints, LayoutCx, LayoutParams, Length, Rect, Size},
+    screen::{Screen, ScreenHandle},
+    semantics::{Role, SemanticNode},
+    style::Style,
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// The direction in which a column or row places its children
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+impl Axis {
+    fn main(self, size: Size) -> f32 {
+        match self {
+            Axis::Horizontal => size.width,
+            Axis::Vertical => size.height,
+        }
+    }
+
+    fn cross(self, size: Size) -> f32 {
+        match self {
+            Axis::Horizontal => size.height,
+            Axis::Vertical => size.width,
+        }
+    }
+
+    fn size(self, main: f32, cross: f32) -> Size {
+        match self {
+            Axis::Horizontal => Size::new(main, cross),
+            Axis::Vertical => Size::new(cross, main),
+        }
+    }
+
+    fn rect(self, main: f32, cross: f32, size: Size) -> Rect {
+        match self {
+            Axis::Horizontal => Rect::from_size(main, cross, size),
+            Axis::Vertical => Rect::from_size(cross, main, size),
+        }
+    }
+
+    fn main_length(self, params: &LayoutParams) -> Length {
+        match self {
+            Axis::Horizontal => params.width,
+            Axis::Vertical => params.height,
+        }
+    }
+}
+
+/// The data behind both `Column` and `Row`
+pub(crate) struct LinearData {
+    pub axis: Axis,
+    pub children: Vec<Rc<RefCell<dyn Draw>>>,
+    pub spacing: f32,
+    pub main_align: MainAlign,
+    pub cross_align: CrossAlign,
+    pub layout: LayoutParams,
+    pub test_id: Option<String>,
+    pub gestures: GestureHandlers,
+    pub style: Style,
+    // What the children returned when they were drawn, for `respond`
+    handles: RefCell<Vec<ScreenHandle>>,
+}
+
+impl LinearData {
+    pub fn new(axis: Axis, children: Vec<Rc<RefCell<dyn Draw>>>) -> Self {
+        Self {
+            axis,
+            children,
+            spacing: 0.,
+            main_align: MainAlign::Start,
+            cross_align: CrossAlign::Start,
+            layout: LayoutParams::default(),
+            test_id: None,
+            gestures: GestureHandlers::default(),
+            style: Style::default(),
+            handles: RefCell::new(vec![]),
+        }
+    }
+
+    /// Weight of a child along the main axis. Match parent counts as weight 1.
+    fn weight(&self, child: &dyn Draw) -> f32 {
+        let params = child.params();
+        if params.weight > 0. {
+            params.weight
+        } else if self.axis.main_length(&params) == Length::MatchParent {
+            1.
+        } else {
+            0.
+        }
+    }
+
+    /// Sizes of the children when the content may be `inner` large.
+    ///
+    /// Children without weight are measured first. The weighted children divide the space
+    /// that is left by their weights.
+    fn child_sizes(&self, cx: &LayoutCx, inner: Size) -> Vec<Size> {
+        let axis = self.axis;
+        let max_main = axis.main(inner);
+        let max_cross = axis.cross(inner);
+        let min_cross = if self.cross_align == CrossAlign::Stretch && max_cross.is_finite() {
+            max_cross
+        } else {
+            0.
+        };
+
+        let weights: Vec<f32> = self
+            .children
+            .iter()
+            .map(|child| self.weight(&*child.borrow()))
+            .collect();
+
+        let mut sizes = vec![Size::ZERO; self.children.len()];
+        let mut used = self.spacing * self.children.len().saturating_sub(1) as f32;
+        for (i, child) in self.children.iter().enumerate() {
+            if weights[i] == 0. {
+                let constraints = Constraints {
+                    min: axis.size(0., min_cross),
+                    max: axis.size(f32::INFINITY, max_cross),
+                };
+                sizes[i] = measure(&*child.borrow(), cx, constraints);
+                used += axis.main(sizes[i]);
+            }
+        }
+
+        let total_weight: f32 = weights.iter().sum();
+        let free = if max_main.is_finite() {
+            (max_main - used).max(0.)
+        } else {
+            0.
+        };
+        for (i, child) in self.children.iter().enumerate() {
+            if weights[i] > 0. {
+                let share = free * weights[i] / total_weight;
+                let constraints = Constraints {
+                    min: axis.size(share, min_cross),
+                    max: axis.size(share, max_cross),
+                };
+                sizes[i] = measure(&*child.borrow(), cx, constraints);
+            }
+        }
+        sizes
+    }
+
+    /// Places the children within `inner`
+    pub(crate) fn arrange(&self, cx: &LayoutCx, inner: Rect) -> Vec<Rect> {
+        let axis = self.axis;
+        let sizes = self.child_sizes(cx, inner.size());
+
+        let count = sizes.len();
+        let used: f32 = sizes.iter().map(|size| axis.main(*size)).sum::<f32>()
+            + self.spacing * count.saturating_sub(1) as f32;
+        let free = (axis.main(inner.size()) - used).max(0.);
+
+        let (lead, gap) = match self.main_align {
+            MainAlign::Start => (0., self.spacing),
+            MainAlign::Center => (free / 2., self.spacing),
+            MainAlign::End => (free, self.spacing),
+            MainAlign::SpaceBetween if count > 1 => (0., self.spacing + free / (count - 1) as f32),
+            MainAlign::SpaceBetween => (0., self.spacing),
+        };
+
+        let (main_start, cross_start) = match axis {
+            Axis::Horizontal => (inner.x, inner.y),
+            Axis::Vertical => (inner.y, inner.x),
+        };
+        let cross_space = axis.cross(inner.size());
+        let mut main = main_start + lead;
+        sizes
+            .into_iter()
+            .map(|size| {
+                let cross_free = (cross_space - axis.cross(size)).max(0.);
+                let cross = cross_start + self.cross_align.factor() * cross_free;
+                let child_rect = axis.rect(main, cross, size);
+                main += axis.main(size) + gap;
+                child_rect
+            })
+            .collect()
+    }
+}
+
+impl Draw for LinearData {
+    fn params(&self) -> LayoutParams {
+        self.layout
+    }
+
+    fn style(&self) -> Style {
+        self.style
+    }
+
+    fn gestures(&self) -> Option<&GestureHandlers> {
+        Some(&self.gestures)
+    }
+
+    fn measure(&self, cx: &LayoutCx, inner: Size) -> Size {
+        let axis = self.axis;
+        let sizes = self.child_sizes(cx, inner);
+
+        let weighted = self
+            .children
+            .iter()
+            .any(|child| self.weight(&*child.borrow()) > 0.);
+        let main = if weighted && axis.main(inner).is_finite() {
+            // Weighted children take whatever there is
+            axis.main(inner)
+        } else {
+            sizes.iter().map(|size| axis.main(*size)).sum::<f32>()
+                + self.spacing * sizes.len().saturating_sub(1) as f32
+        };
+        let cross = sizes
+            .iter()
+            .map(|size| axis.cross(*size))
+            .fold(0., f32::max);
+        axis.size(main, cross)
+    }
+
+    fn draw(&self, screen: &mut Screen, rect: Rect) -> ScreenHandle {
+        let context = screen.context.clone();
+
+        let rects = self.arrange(&LayoutCx::new(screen.backend), rect);
+        let handles = self
+            .children
+            .iter()
+            .zip(rects)
+            .map(|(child, child_rect)| {
+                let mut screen = context.screen(screen.backend);
+                layout::draw(&*child.borrow(), &mut screen, child_rect)
+            })
+            .collect();
+        self.handles.replace(handles);
+
+        let interaction = screen.backend.interact(rect, Sense::Hover);
+        ScreenHandle::new(interaction)
+    }
+
+    fn respond(&self, _handle: &ScreenHandle) {
+        for (child, handle) in self.children.iter().zip(self.handles.borrow().iter()) {
+            layout::respond(&*child.borrow(), handle);
+        }
+    }
+
+    fn semantics(&self, cx: &LayoutCx, rect: Rect) -> SemanticNode {
+        let children = self
+            .children
+            .iter()
+            .zip(self.arrange(cx, rect))
+            .map(|(child, child_rect)| layout::semantics(&*child.borrow(), cx, child_rect))
+            .collect();
+        let role = match self.axis {
+            Axis::Horizontal => Role::Row,
+            Axis::Vertical => Role::Column,
+        };
+        SemanticNode {
+            test_id: self.test_id.clone(),
+            children,
+            ..SemanticNode::new(role, rect)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Axis, LinearData};
+    use crate::{
+        drawables::traits::Draw,
+        layout::{
+            measure,
+            tests::{cx, Content},
+            Constraints, LayoutParams, Length, Rect, Size,
+        },
+        structures::{CrossAlign, MainAlign},
+        style::Insets,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    fn child(width: Length, height: Length) -> Rc<RefCell<dyn Draw>> {
+        let params = LayoutParams {
+            width,
+            height,
+            ..Default::default()
+        };
+        Rc::new(RefCell::new(Content(params, Size::new(20., 20.))))
+    }
+
+    fn weighted(weight: f32) -> Rc<RefCell<dyn Draw>> {
+        let params = LayoutParams {
+            weight,
+            ..Default::default()
+        };
+        Rc::new(RefCell::new(Content(params, Size::new(20., 20.))))
+    }
+
+    const AREA: Rect = Rect::new(0., 0., 200., 300.);
+
+    #[test]
+    fn fill_takes_the_space_that_is_left() {
+        let mut column = LinearData::new(
+            Axis::Vertical,
+            vec![
+                child(Length::Fixed(100.), Length::Fixed(50.)),
+                child(Length::Fixed(100.), Length::MatchParent),
+                child(Length::Fixed(100.), Length::Fixed(30.)),
+            ],
+        );
+        column.spacing = 10.;
+        let rects = column.arrange(&cx(), AREA);
+        assert_eq!(rects[0].height, 50.);
+        assert_eq!(rects[1].height, 300. - 50. - 30. - 2. * 10.);
+        assert_eq!(rects[2].max_y(), 300.);
+    }
+
+    #[test]
+    fn weights_divide_the_space() {
+        let row = LinearData::new(
+            Axis::Horizontal,
+            vec![
+                child(Length::Fixed(50.), Length::WrapContent),
+                weighted(1.),
+                weighted(2.),
+            ],
+        );
+        let rects = row.arrange(&cx(), AREA);
+        assert_eq!(rects[1], Rect::new(50., 0., 50., 20.));
+        assert_eq!(rects[2], Rect::new(100., 0., 100., 20.));
+    }
+
+    #[test]
+    fn alignment() {
+        let mut column = LinearData::new(
+            Axis::Vertical,
+            vec![
+                child(Length::Fixed(100.), Length::Fixed(50.)),
+                child(Length::Fixed(60.), Length::Fixed(30.)),
+            ],
+        );
+        column.main_align = MainAlign::End;
+        column.cross_align = CrossAlign::Center;
+        let rects = column.arrange(&cx(), AREA);
+        assert_eq!(rects[0], Rect::new(50., 300. - 80., 100., 50.));
+        assert_eq!(rects[1], Rect::new(70., 300. - 30., 60., 30.));
+
+        column.cross_align = CrossAlign::Stretch;
+        let rects = column.arrange(&cx(), AREA);
+        assert_eq!(rects[1].width, 200.);
+    }
+
+    #[test]
+    fn wrap_content_size() {
+        let mut row = LinearData::new(
+            Axis::Horizontal,
+            vec![
+                child(Length::Fixed(50.), Length::Fixed(10.)),
+                child(Length::Fixed(30.), Length::Fixed(40.)),
+            ],
+        );
+        row.spacing = 5.;
+        row.style.padding = Insets::all(2.);
+        let constraints = Constraints::loose(AREA.size());
+        assert_eq!(measure(&row, &cx(), constraints), Size::new(89., 44.));
+    }
+}
@@ -0,0 +1,848 @@
+use super::linear::{Axis, LinearData};
+use crate::{
+    backend::{Backend, ButtonColors, MeasureOnly, Pointer, Sense},
+    color::Color,
+    drawables::traits::{common_modifiers, Draw},
+    gestures::GestureHandlers,
+    layout::{self, measure, Constraints, LayoutCx, LayoutParams, Length, Rect, Size},
+    screen::{Screen, ScreenContext, ScreenHandle},
+    semantics::{Role, SemanticNode},
+    store::Store,
+    style::Style,
+    task::Task,
+};
+use std::{
+    cell::RefCell,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
+
+/// When there is no limit to the size
+const DEFAULT_SIZE: Size = Size::new(200., 300.);
+/// How far the pointer moves before a press is a drag
+const SLOP: f32 = 8.;
+/// How far the list is pulled down past its top to refresh
+const REFRESH_DISTANCE: f32 = 64.;
+/// The space above the rows while refreshing
+const SPINNER_SPACE: f32 = 48.;
+const SPINNER: f32 = 24.;
+
+/// Where a list is scrolled to, and what the pointer does to it. Pages keep it, so the list
+/// stays where it was between frames. Clones refer to the same list.
+#[derive(Clone, Default)]
+pub struct ListState(Store<State>);
+
+#[derive(Default)]
+struct State {
+    position: Position,
+    gesture: Gesture,
+    // Whether the refresh the list started is still running
+    refreshing: Option<Running>,
+}
+
+type Running = Box<dyn Fn() -> bool>;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Position {
+    // The row at the top
+    first: usize,
+    // The key of that row, to find it back when rows are added or removed above it
+    key: Option<u64>,
+    // How far the first row is scrolled past the top
+    offset: f32,
+    // The count of rows when the end was reported last
+    end_reported: Option<usize>,
+}
+
+/// What the pointer does to the list
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Gesture {
+    press: Option<Press>,
+    // How far the list is pulled down past its top
+    pull: f32,
+    // The row with its swipe actions shown, and how far they are shown
+    swiped: Option<(u64, f32)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Press {
+    // Where the pointer was pressed, and where it was in the last frame
+    start: (f32, f32),
+    last: (f32, f32),
+    // Decided once the pointer moved far enough
+    axis: Option<Axis>,
+    // The row it was pressed on, and how far its swipe actions were shown then
+    row: Option<usize>,
+    swiped: f32,
+}
+
+impl ListState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shows the row at `index` at the top, or as far up as the rows after it allow
+    pub fn scroll_to(&self, index: usize) {
+        self.0.update(|state| {
+            state.position.first = index;
+            state.position.key = None;
+            state.position.offset = 0.;
+        });
+    }
+
+    /// The row at the top
+    pub fn first_visible(&self) -> usize {
+        self.0.read().position.first
+    }
+
+    /// Whether the refresh that pulling the list started is still running
+    pub fn is_refreshing(&self) -> bool {
+        let state = self.0.read();
+        state.refreshing.as_ref().map_or(false, |running| running())
+    }
+}
+
+/// Rows below each other that scroll. Only the rows in view are built and drawn, so it can
+/// have thousands of them. Dragging with the pointer scrolls it as well, and can pull it to
+/// refresh or swipe a row to show its actions.
+#[derive(Clone)]
+pub struct List(pub(crate) Rc<RefCell<ListData>>);
+
+impl List {
+    pub(crate) fn new(
+        context: ScreenContext,
+        state: ListState,
+        count: usize,
+        build: impl Fn(&mut Screen, usize) + 'static,
+    ) -> Self {
+        Self(Rc::new(RefCell::new(ListData {
+            context,
+            state,
+            count,
+            build: Box::new(build),
+            key: None,
+            is_header: None,
+            on_end_reached: None,
+            on_refresh: None,
+            swipe_actions: vec![],
+            spacing: 0.,
+            layout: LayoutParams {
+                width: Length::MatchParent,
+                height: Length::MatchParent,
+                ..Default::default()
+            },
+            test_id: None,
+            gestures: GestureHandlers::default(),
+            style: Style::default(),
+            drawn: RefCell::default(),
+        })))
+    }
+
+    /// Space between the rows
+    pub fn spacing(self, spacing: u16) -> Self {
+        self.0.borrow_mut().spacing = spacing.into();
+        self
+    }
+    /// Tells rows apart, so the list stays at the same row when rows are added or removed
+    /// above it
+    pub fn key<K: Hash>(self, key: impl Fn(usize) -> K + 'static) -> Self {
+        self.0.borrow_mut().key = Some(Box::new(move |index| {
+            let mut hasher = DefaultHasher::new();
+            key(index).hash(&mut hasher);
+            hasher.finish()
+        }));
+        self
+    }
+    /// The rows that are section headers. The header of the rows in view stays at the top,
+    /// until the next header pushes it up.
+    pub fn sticky_headers(self, is_header: impl Fn(usize) -> bool + 'static) -> Self {
+        self.0.borrow_mut().is_header = Some(Box::new(is_header));
+        self
+    }
+    /// Called when the last row comes into view, to load more. It is called again once the
+    /// count changed.
+    pub fn on_end_reached(self, on_end_reached: impl FnMut() + 'static) -> Self {
+        self.0.borrow_mut().on_end_reached = Some(RefCell::new(Box::new(on_end_reached)));
+        self
+    }
+    /// Pulling the list down past its top calls `on_refresh`. A spinner shows above the rows
+    /// until the task it returns is done.
+    pub fn on_refresh<T: 'static>(self, mut on_refresh: impl FnMut() -> Task<T> + 'static) -> Self {
+        self.0.borrow_mut().on_refresh = Some(RefCell::new(Box::new(move || {
+            let task = on_refresh();
+            Box::new(move || task.is_running())
+        })));
+        self
+    }
+    /// Swiping a row to the left shows a button for each action, in the order they were
+    /// added. A tap on one calls `on_action` with the index of the row.
+    pub fn swipe_action(
+        self,
+        label: impl Into<String>,
+        color: Color,
+        on_action: impl FnMut(usize) + 'static,
+    ) -> Self {
+        self.0.borrow_mut().swipe_actions.push(SwipeAction {
+            label: label.into(),
+            color,
+            on_action: RefCell::new(Box::new(on_action)),
+        });
+        self
+    }
+}
+
+common_modifiers!(List);
+
+pub(crate) struct ListData {
+    // What the rows are built with
+    context: ScreenContext,
+    pub state: ListState,
+    pub count: usize,
+    build: Box<BuildRow>,
+    key: Option<Box<dyn Fn(usize) -> u64>>,
+    is_header: Option<Box<dyn Fn(usize) -> bool>>,
+    pub on_end_reached: Option<RefCell<Box<dyn FnMut()>>>,
+    on_refresh: Option<Refresh>,
+    swipe_actions: Vec<SwipeAction>,
+    pub spacing: f32,
+    pub layout: LayoutParams,
+    pub test_id: Option<String>,
+    pub gestures: GestureHandlers,
+    pub style: Style,
+    // What was drawn, for `respond`
+    drawn: RefCell<Drawn>,
+}
+
+type BuildRow = dyn Fn(&mut Screen, usize);
+type Refresh = RefCell<Box<dyn FnMut() -> Running>>;
+
+struct SwipeAction {
+    label: String,
+    color: Color,
+    on_action: RefCell<Box<dyn FnMut(usize)>>,
+}
+
+#[derive(Default)]
+struct Drawn {
+    rows: Vec<(LinearData, ScreenHandle)>,
+    // The state when it was drawn, and what it should become
+    read: (Position, Gesture),
+    position: Position,
+    gesture: Gesture,
+    // The swipe action that was tapped, with the row
+    action: Option<(usize, usize)>,
+    refresh: bool,
+    refreshed: bool,
+    end_in_view: bool,
+}
+
+/// Where everything goes in a frame
+struct Layout {
+    // The rows in view, and the header that sticks to the top
+    placed: Vec<(usize, Rect)>,
+    sticky: Option<(usize, Rect)>,
+    // The row with its swipe actions shown, and where the actions go
+    swiped: Option<(usize, Rect)>,
+    spinner: Option<Rect>,
+}
+
+/// The rows of one frame, built when they are first needed
+struct Rows<'a> {
+    list: &'a ListData,
+    backend: &'a mut dyn Backend,
+    width: f32,
+    built: HashMap<usize, (LinearData, f32)>,
+}
+
+impl<'a> Rows<'a> {
+    fn new(list: &'a ListData, backend: &'a mut dyn Backend, width: f32) -> Self {
+        Self {
+            list,
+            backend,
+            width,
+            built: HashMap::new(),
+        }
+    }
+
+    fn height(&mut self, index: usize) -> f32 {
+        if let Some((_, height)) = self.built.get(&index) {
+            return *height;
+        }
+        let row = self.list.build_row(self.backend, index);
+        let constraints = Constraints {
+            min: Size::new(self.width, 0.),
+            max: Size::new(self.width, f32::INFINITY),
+        };
+        let height = measure(&row, &LayoutCx::new(self.backend), constraints).height;
+        self.built.insert(index, (row, height));
+        height
+    }
+}
+
+impl ListData {
+    // The drawables the page queues for a row, in a column
+    fn build_row(&self, backend: &mut dyn Backend, index: usize) -> LinearData {
+        let spacing = backend.item_spacing();
+        let mut screen = self.context.screen(backend);
+        (self.build)(&mut screen, index);
+        let mut row = LinearData::new(Axis::Vertical, screen.queue.into_inner());
+        row.spacing = spacing;
+        row.layout.width = Length::MatchParent;
+        row
+    }
+
+    // Tells the rows apart, also without keys
+    fn row_id(&self, index: usize) -> u64 {
+        self.key
+            .as_ref()
+            .map_or(index as u64, |key_of| key_of(index))
+    }
+
+    fn action_widths(&self, backend: &dyn Backend) -> Vec<f32> {
+        let max = Size::new(f32::INFINITY, f32::INFINITY);
+        self.swipe_actions
+            .iter()
+            .map(|action| backend.measure_button(&action.label, max).width)
+            .collect()
+    }
+
+    // The swipe actions within `strip`, from the left
+    fn action_rects<'a>(
+        &'a self,
+        strip: Rect,
+        widths: &'a [f32],
+    ) -> impl Iterator<Item = (&'a SwipeAction, Rect)> + 'a {
+        let mut x = strip.x;
+        self.swipe_actions
+            .iter()
+            .zip(widths)
+            .map(move |(action, width)| {
+                let rect = Rect::new(x, strip.y, *width, strip.height);
+                x += width;
+                (action, rect)
+            })
+    }
+
+    // The state as it is, and whether a refresh is running
+    fn read(&self) -> (Position, Gesture, Option<bool>) {
+        let state = self.state.0.read();
+        let running = state.refreshing.as_ref().map(|running| running());
+        (state.position, state.gesture, running)
+    }
+
+    /// Where the list is after scrolling `delta` from `position`, with the first row in view
+    /// and no space left below the last row
+    fn scrolled(&self, rows: &mut Rows, height: f32, position: Position, delta: f32) -> Position {
+        let count = self.count;
+        if count == 0 {
+            return Position {
+                end_reported: position.end_reported,
+                ..Position::default()
+            };
+        }
+        let mut first = position.first;
+        if let (Some(key), Some(key_of)) = (position.key, &self.key) {
+            if first >= count || key_of(first) != key {
+                first = (0..count)
+                    .find(|index| key_of(*index) == key)
+                    .unwrap_or(first);
+            }
+        }
+        let mut first = first.min(count - 1);
+        let mut offset = position.offset - delta;
+
+        let up = |rows: &mut Rows, first: &mut usize, offset: &mut f32| {
+            while *offset < 0. && *first > 0 {
+                *first -= 1;
+                *offset += rows.height(*first) + self.spacing;
+            }
+            *offset = offset.max(0.);
+        };
+        while first + 1 < count {
+            let row = rows.height(first) + self.spacing;
+            if offset < row {
+                break;
+            }
+            offset -= row;
+            first += 1;
+        }
+        up(rows, &mut first, &mut offset);
+
+        let (mut bottom, mut index) = (-offset, first);
+        while index < count && bottom < height {
+            bottom += rows.height(index) + self.spacing;
+            index += 1;
+        }
+        if index == count && bottom - self.spacing < height {
+            offset -= height - (bottom - self.spacing);
+            up(rows, &mut first, &mut offset);
+        }
+
+        Position {
+            first,
+            key: self.key.as_ref().map(|key_of| key_of(first)),
+            offset,
+            end_reported: position.end_reported,
+        }
+    }
+
+    /// The rows in view, from the top
+    fn place(&self, rows: &mut Rows, rect: Rect, position: Position) -> Vec<(usize, Rect)> {
+        let mut placed = vec![];
+        let (mut y, mut index) = (rect.y - position.offset, position.first);
+        while index < self.count && y < rect.max_y() {
+            let height = rows.height(index);
+            placed.push((index, Rect::new(rect.x, y, rect.width, height)));
+            y += height + self.spacing;
+            index += 1;
+        }
+        placed
+    }
+
+    /// Where the rows, the swipe actions and the spinner go
+    fn layout(
+        &self,
+        rows: &mut Rows,
+        rect: Rect,
+        position: Position,
+        gesture: &Gesture,
+        refreshing: bool,
+        actions_width: f32,
+    ) -> Layout {
+        let spinner_space = if refreshing { SPINNER_SPACE } else { 0. };
+        let top = spinner_space.max(gesture.pull.min(2. * REFRESH_DISTANCE));
+        let content = Rect {
+            y: rect.y + top,
+            ..rect
+        };
+        let mut placed = self.place(rows, content, position);
+        let sticky = self.sticky(rows, content, &placed);
+        let swiped = gesture.swiped.and_then(|(id, shown)| {
+            let (index, row) = placed
+                .iter_mut()
+                .find(|(index, _)| self.row_id(*index) == id)?;
+            row.x -= shown;
+            Some((
+                *index,
+                Rect::new(row.max_x(), row.y, actions_width, row.height),
+            ))
+        });
+        let spinner = (top > 0.).then(|| {
+            let x = rect.x + (rect.width - SPINNER) / 2.;
+            Rect::new(x, rect.y + (top - SPINNER) / 2., SPINNER, SPINNER)
+        });
+        Layout {
+            placed,
+            sticky,
+            swiped,
+            spinner,
+        }
+    }
+
+    /// Follows the pointer that drags to scroll, pulls to refresh or swipes a row. Returns how
+    /// far the rows scroll, and whether to refresh.
+    fn follow(
+        &self,
+        gesture: &mut Gesture,
+        pointer: Pointer,
+        rect: Rect,
+        layout: &Layout,
+        at_top: bool,
+        actions_width: f32,
+    ) -> (f32, bool) {
+        let Some(pos) = pointer.pos else {
+            return (0., false);
+        };
+        if pointer.pressed && rect.contains(pos) {
+            let row = layout
+                .placed
+                .iter()
+                .find(|(_, row)| pos.1 >= row.y && pos.1 < row.max_y())
+                .map(|(index, _)| *index);
+            let swiped = match (row, gesture.swiped) {
+                (Some(row), Some((id, shown))) if self.row_id(row) == id => shown,
+                _ => 0.,
+            };
+            gesture.press = Some(Press {
+                start: pos,
+                last: pos,
+                axis: None,
+                row,
+                swiped,
+            });
+        }
+        let Some(mut press) = gesture.press else {
+            return (0., false);
+        };
+
+        let (dx, dy) = (pos.0 - press.start.0, pos.1 - press.start.1);
+        if press.axis.is_none() && dx.abs().max(dy.abs()) > SLOP {
+            press.axis = Some(if dx.abs() > dy.abs() {
+                Axis::Horizontal
+            } else {
+                Axis::Vertical
+            });
+        }
+        let mut scroll = 0.;
+        match (press.axis, press.row) {
+            (Some(Axis::Vertical), _) => {
+                gesture.swiped = None;
+                scroll = pos.1 - press.last.1;
+                if self.on_refresh.is_some() && (gesture.pull > 0. || at_top && scroll > 0.) {
+                    let pull = gesture.pull + scroll;
+                    gesture.pull = pull.max(0.);
+                    // Pushing back up scrolls once the pull is gone
+                    scroll = pull.min(0.);
+                }
+            }
+            (Some(Axis::Horizontal), Some(row)) if actions_width > 0. => {
+                let shown = (press.swiped - dx).clamp(0., actions_width);
+                gesture.swiped = Some((self.row_id(row), shown));
+            }
+            _ => {}
+        }
+        press.last = pos;
+        gesture.press = Some(press);
+
+        let mut refresh = false;
+        if pointer.released {
+            match press.axis {
+                Some(Axis::Vertical) => {
+                    refresh = gesture.pull >= REFRESH_DISTANCE;
+                    gesture.pull = 0.;
+                }
+                // Snaps open or closed
+                Some(Axis::Horizontal) => {
+                    gesture.swiped = gesture
+                        .swiped
+                        .filter(|(_, shown)| *shown > actions_width / 2.)
+                        .map(|(id, _)| (id, actions_width));
+                }
+                // A tap anywhere but on the actions closes them
+                None => {
+                    if !layout
+                        .swiped
+                        .map_or(false, |(_, strip)| strip.contains(pos))
+                    {
+                        gesture.swiped = None;
+                    }
+                }
+            }
+            gesture.press = None;
+        }
+        (scroll, refresh)
+    }
+
+    /// The header that sticks to the top, and where it goes
+    fn sticky(
+        &self,
+        rows: &mut Rows,
+        rect: Rect,
+        placed: &[(usize, Rect)],
+    ) -> Option<(usize, Rect)> {
+        let is_header = self.is_header.as_ref()?;
+        let first = placed.first()?.0;
+        let header = (0..=first).rev().find(|index| is_header(*index))?;
+        let height = rows.height(header);
+        // The next header pushes it up
+        let y = placed
+            .iter()
+            .find(|(index, _)| *index > header && is_header(*index))
+            .map_or(rect.y, |(_, next)| rect.y.min(next.y - height));
+        Some((header, Rect::new(rect.x, y, rect.width, height)))
+    }
+}
+
+impl Draw for ListData {
+    fn params(&self) -> LayoutParams {
+        self.layout
+    }
+
+    fn style(&self) -> Style {
+        self.style
+    }
+
+    fn gestures(&self) -> Option<&GestureHandlers> {
+        Some(&self.gestures)
+    }
+
+    fn measure(&self, _cx: &LayoutCx, max: Size) -> Size {
+        let finite = |max: f32, default| if max.is_finite() { max } else { default };
+        Size::new(
+            finite(max.width, DEFAULT_SIZE.width),
+            finite(max.height, DEFAULT_SIZE.height),
+        )
+    }
+
+    fn draw(&self, screen: &mut Screen, rect: Rect) -> ScreenHandle {
+        let interaction = screen.backend.interact(rect, Sense::Scroll);
+        let pointer = screen.backend.pointer();
+        let widths = self.action_widths(screen.backend);
+        let actions_width = widths.iter().sum();
+        let (read_position, read_gesture, running) = self.read();
+        let refreshing = running == Some(true);
+
+        let mut rows = Rows::new(self, screen.backend, rect.width);
+        let mut gesture = read_gesture;
+        let position = self.scrolled(&mut rows, rect.height, read_position, 0.);
+        let at_top = position.first == 0 && position.offset == 0.;
+        let before = self.layout(
+            &mut rows,
+            rect,
+            position,
+            &gesture,
+            refreshing,
+            actions_width,
+        );
+        let (drag, refresh) =
+            self.follow(&mut gesture, pointer, rect, &before, at_top, actions_width);
+        let delta = interaction.scroll.1 + drag;
+        let position = self.scrolled(&mut rows, rect.height, position, delta);
+        let layout = self.layout(
+            &mut rows,
+            rect,
+            position,
+            &gesture,
+            refreshing,
+            actions_width,
+        );
+        let mut built = rows.built;
+
+        let clip = screen.backend.clip_rect();
+        screen.backend.set_clip_rect(rect.intersect(clip));
+        let mut drawn = vec![];
+        let mut draw_row = |screen: &mut Screen, index: usize, row_rect: Rect| {
+            if let Some((row, _)) = built.remove(&index) {
+                let mut row_screen = self.context.screen(screen.backend);
+                let handle = layout::draw(&row, &mut row_screen, row_rect);
+                drawn.push((row, handle));
+            }
+        };
+        for &(index, row_rect) in &layout.placed {
+            if layout.sticky.map(|(header, _)| header) != Some(index) {
+                draw_row(screen, index, row_rect);
+            }
+        }
+        let colors = &self.context.theme.colors;
+        if let Some((header, header_rect)) = layout.sticky {
+            // Covers the rows that scroll below it
+            screen.backend.fill_rect(header_rect, colors.background);
+            draw_row(screen, header, header_rect);
+        }
+        let mut action = None;
+        if let Some((row, strip)) = layout.swiped {
+            for (index, (swipe_action, rect)) in self.action_rects(strip, &widths).enumerate() {
+                let button_colors = ButtonColors {
+                    fill: swipe_action.color,
+                    text: colors.on_primary,
+                };
+                let label = &swipe_action.label;
+                if screen
+                    .backend
+                    .button(rect, label, button_colors, true)
+                    .clicked
+                {
+                    action = Some((index, row));
+                }
+            }
+        }
+        if let Some(spinner) = layout.spinner {
+            screen.backend.spinner(spinner, colors.primary);
+        }
+        screen.backend.set_clip_rect(clip);
+
+        let last = layout.placed.last().map(|(index, _)| index + 1);
+        self.drawn.replace(Drawn {
+            rows: drawn,
+            read: (read_position, read_gesture),
+            position,
+            gesture,
+            action,
+            refresh: refresh && !refreshing,
+            refreshed: running == Some(false),
+            end_in_view: last == Some(self.count),
+        });
+        ScreenHandle::new(interaction)
+    }
+
+    fn respond(&self, _handle: &ScreenHandle) {
+        let drawn = self.drawn.take();
+        let (mut position, mut gesture) = (drawn.position, drawn.gesture);
+        let reached = drawn.end_in_view && position.end_reported != Some(self.count);
+        if reached {
+            position.end_reported = Some(self.count);
+        }
+        if drawn.action.is_some() {
+            gesture.swiped = None;
+        }
+        // Unless a callback scrolled it while the screen was drawn
+        let (read_position, read_gesture, _) = self.read();
+        let unchanged = (read_position, read_gesture) == drawn.read;
+        if unchanged && (position, gesture) != drawn.read {
+            self.state.0.update(|state| {
+                state.position = position;
+                state.gesture = gesture;
+            });
+        }
+        if drawn.refreshed {
+            self.state.0.update(|state| state.refreshing = None);
+        }
+
+        for (row, handle) in &drawn.rows {
+            row.respond(handle);
+        }
+        if let Some((action, row)) = drawn.action {
+            (self.swipe_actions[action].on_action.borrow_mut())(row);
+        }
+        if let (true, Some(on_refresh)) = (drawn.refresh, &self.on_refresh) {
+            let running = (on_refresh.borrow_mut())();
+            self.state
+                .0
+                .update(|state| state.refreshing = Some(running));
+        }
+        if let (true, Some(on_end_reached)) = (reached, &self.on_end_reached) {
+            (on_end_reached.borrow_mut())();
+        }
+    }
+
+    fn semantics(&self, cx: &LayoutCx, rect: Rect) -> SemanticNode {
+        let mut backend = MeasureOnly(cx.backend);
+        let widths = self.action_widths(&backend);
+        let (position, gesture, running) = self.read();
+        let mut rows = Rows::new(self, &mut backend, rect.width);
+        let position = self.scrolled(&mut rows, rect.height, position, 0.);
+        let refreshing = running == Some(true);
+        let actions_width = widths.iter().sum();
+        let layout = self.layout(
+            &mut rows,
+            rect,
+            position,
+            &gesture,
+            refreshing,
+            actions_width,
+        );
+        let built = rows.built;
+
+        let header = layout.sticky.map(|(header, _)| header);
+        let mut children: Vec<SemanticNode> = layout
+            .placed
+            .into_iter()
+            .filter(|(index, _)| Some(*index) != header)
+            .chain(layout.sticky)
+            .filter_map(|(index, row_rect)| {
+                let (row, _) = built.get(&index)?;
+                Some(layout::semantics(row, cx, row_rect))
+            })
+            .collect();
+        if let Some((_, strip)) = layout.swiped {
+            children.extend(
+                self.action_rects(strip, &widths)
+                    .map(|(action, rect)| SemanticNode {
+                        label: Some(action.label.clone()),
+                        ..SemanticNode::new(Role::Button, rect)
+                    }),
+            );
+        }
+        children.extend(
+            layout
+                .spinner
+                .map(|spinner| SemanticNode::new(Role::Spinner, spinner)),
+        );
+        SemanticNode {
+            test_id: self.test_id.clone(),
+            children,
+            ..SemanticNode::new(Role::List, rect)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ListState;
+    use crate::{
+        layout::tests::{TestBackend, TestRoute},
+        navigation::Navigator,
+        screen::Screen,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    #[test]
+    fn only_the_rows_in_view() {
+        let state = ListState::new();
+        let built = Rc::new(Cell::new(0));
+        let mut backend = TestBackend;
+        let mut screen = Screen::new(&mut backend, Navigator::new(TestRoute));
+        let counter = built.clone();
+        screen
+            .list(&state, 1000, move |screen, index| {
+                counter.set(counter.get() + 1);
+                if index % 10 == 0 {
+                    screen.text(format!("Section {}", index / 10));
+                } else {
+                    screen.text(format!("Row {index}"));
+                }
+            })
+            .sticky_headers(|index| index % 10 == 0);
+
+        // Rows are 16 high in 300
+        let tree = screen.semantics();
+        assert!(tree.find_by_text("Row 18").is_some());
+        assert!(tree.find_by_text("Row 19").is_none());
+        assert_eq!(built.get(), 19);
+
+        // The rows of section 50 before 505 are above the top, its header is not
+        state.scroll_to(505);
+        let tree = screen.semantics();
+        assert_eq!(tree.find_by_text("Section 50").unwrap().bounds.y, 0.);
+        assert_eq!(tree.find_by_text("Row 506").unwrap().bounds.y, 16.);
+        assert!(tree.find_by_text("Row 504").is_none());
+
+        // Not past the last row
+        state.scroll_to(999);
+        let tree = screen.semantics();
+        assert_eq!(tree.find_by_text("Row 999").unwrap().bounds.max_y(), 300.);
+        assert_eq!(tree.find_by_text("Section 98").unwrap().bounds.y, 0.);
+    }
+
+    #[cfg(feature = "egui")]
+    #[test]
+    fn wheel_scrolls_until_the_end() {
+        use crate::{layout::tests::egui_frame, store::Store};
+        use egui::{pos2, vec2, Context, Event};
+
+        let ctx = Context::default();
+        let state = ListState::new();
+        let count = Store::new(50);
+        let frame = |scroll: f32| {
+            let events = vec![
+                Event::PointerMoved(pos2(100., 100.)),
+                Event::Scroll(vec2(0., scroll)),
+            ];
+            egui_frame(&ctx, None, events, |screen| {
+                let more = count.clone();
+                screen
+                    .list(&state, count.get(), |screen, index| {
+                        screen.text(format!("Row {index}"));
+                    })
+                    .on_end_reached(move || more.update(|count| *count += 50));
+            });
+        };
+
+        frame(0.);
+        frame(-100.);
+        let first = state.first_visible();
+        assert!(first > 0);
+        frame(100.);
+        assert!(state.first_visible() < first);
+        assert_eq!(count.get(), 50);
+
+        // Loads more once at the end, and again at the end of what it loaded
+        frame(-10000.);
+        assert_eq!(count.get(), 100);
+        frame(-10.);
+        assert_eq!(count.get(), 100);
+        frame(-10000.);
+        assert_eq!(count.get(), 150);
+    }
+}
@@ -1 +1,38 @@
-mod column;
+pub mod column;
+pub(crate) mod linear;
+pub mod list;
+pub mod row;
+pub mod stack;
+
+/// Where children go along the direction of a column or row
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MainAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// First and last child at the edges, the space left is divided between the children
+    SpaceBetween,
+}
+
+/// Where children go across the direction of a column or row
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CrossAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// Children take the full width of a column or the full height of a row
+    Stretch,
+}
+
+impl CrossAlign {
+    /// Part of the free space that goes before the child
+    pub(crate) fn factor(self) -> f32 {
+        match self {
+            CrossAlign::Start | CrossAlign::Stretch => 0.,
+            CrossAlign::Center => 0.5,
+            CrossAlign::End => 1.,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use super::{
+    linear::{Axis, LinearData},
+    CrossAlign, MainAlign,
+};
+use crate::drawables::traits::{common_modifiers, Draw};
+use std::{cell::RefCell, rc::Rc};
+
+/// Places its children next to each other
+#[derive(Clone)]
+pub struct Row(pub(crate) Rc<RefCell<LinearData>>);
+
+impl Row {
+    pub(crate) fn new(children: Vec<Rc<RefCell<dyn Draw>>>) -> Self {
+        Self(Rc::new(RefCell::new(LinearData::new(
+            Axis::Horizontal,
+            children,
+        ))))
+    }
+
+    pub fn spacing(self, spacing: u16) -> Self {
+        self.0.borrow_mut().spacing = spacing.into();
+        self
+    }
+    /// Horizontal alignment of the children
+    pub fn main_align(self, align: MainAlign) -> Self {
+        self.0.borrow_mut().main_align = align;
+        self
+    }
+    /// Vertical alignment of the children
+    pub fn cross_align(self, align: CrossAlign) -> Self {
+        self.0.borrow_mut().cross_align = align;
+        self
+    }
+}
+
+common_modifiers!(Row);
@@ -0,0 +1,193 @@
+use super::CrossAlign;
+use crate::{
+    backend::Sense,
+    drawables::traits::{common_modifiers, Draw},
+    gestures::GestureHandlers,
+    layout::{self, measure, Constraints, LayoutCx, LayoutParams, Length, Rect, Size},
+    screen::{Screen, ScreenHandle},
+    semantics::{Role, SemanticNode},
+    style::Style,
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// Places its children on top of each other. The first child is at the bottom.
+#[derive(Clone)]
+pub struct Stack(pub(crate) Rc<RefCell<StackData>>);
+
+impl Stack {
+    pub(crate) fn new(children: Vec<Rc<RefCell<dyn Draw>>>) -> Self {
+        let data = StackData {
+            children,
+            horizontal: CrossAlign::Start,
+            vertical: CrossAlign::Start,
+            layout: LayoutParams::default(),
+            test_id: None,
+            gestures: GestureHandlers::default(),
+            style: Style::default(),
+            handles: RefCell::new(vec![]),
+        };
+        Self(Rc::new(RefCell::new(data)))
+    }
+
+    /// Alignment of the children within the stack
+    pub fn align(self, horizontal: CrossAlign, vertical: CrossAlign) -> Self {
+        let mut data = self.0.borrow_mut();
+        data.horizontal = horizontal;
+        data.vertical = vertical;
+        drop(data);
+        self
+    }
+}
+
+common_modifiers!(Stack);
+
+pub(crate) struct StackData {
+    pub children: Vec<Rc<RefCell<dyn Draw>>>,
+    pub horizontal: CrossAlign,
+    pub vertical: CrossAlign,
+    pub layout: LayoutParams,
+    pub test_id: Option<String>,
+    pub gestures: GestureHandlers,
+    pub style: Style,
+    // What the children returned when they were drawn, for `respond`
+    handles: RefCell<Vec<ScreenHandle>>,
+}
+
+impl StackData {
+    fn child_size(&self, cx: &LayoutCx, child: &dyn Draw, inner: Size) -> Size {
+        let params = child.params();
+        let stretch = |align: CrossAlign, length: Length, max: f32| {
+            (align == CrossAlign::Stretch || length == Length::MatchParent) && max.is_finite()
+        };
+        let min = Size::new(
+            if stretch(self.horizontal, params.width, inner.width) {
+                inner.width
+            } else {
+                0.
+            },
+            if stretch(self.vertical, params.height, inner.height) {
+                inner.height
+            } else {
+                0.
+            },
+        );
+        measure(child, cx, Constraints { min, max: inner })
+    }
+
+    /// Places the children within `inner`
+    pub(crate) fn arrange(&self, cx: &LayoutCx, inner: Rect) -> Vec<Rect> {
+        self.children
+            .iter()
+            .map(|child| {
+                let size = self.child_size(cx, &*child.borrow(), inner.size());
+                let x = inner.x + self.horizontal.factor() * (inner.width - size.width);
+                let y = inner.y + self.vertical.factor() * (inner.height - size.height);
+                Rect::from_size(x, y, size)
+            })
+            .collect()
+    }
+}
+
+impl Draw for StackData {
+    fn params(&self) -> LayoutParams {
+        self.layout
+    }
+
+    fn style(&self) -> Style {
+        self.style
+    }
+
+    fn gestures(&self) -> Option<&GestureHandlers> {
+        Some(&self.gestures)
+    }
+
+    fn measure(&self, cx: &LayoutCx, inner: Size) -> Size {
+        // As large as the largest child
+        self.children.iter().fold(Size::ZERO, |size, child| {
+            let child = self.child_size(cx, &*child.borrow(), inner);
+            Size::new(size.width.max(child.width), size.height.max(child.height))
+        })
+    }
+
+    fn draw(&self, screen: &mut Screen, rect: Rect) -> ScreenHandle {
+        let context = screen.context.clone();
+
+        let rects = self.arrange(&LayoutCx::new(screen.backend), rect);
+        let handles = self
+            .children
+            .iter()
+            .zip(rects)
+            .map(|(child, child_rect)| {
+                let mut screen = context.screen(screen.backend);
+                layout::draw(&*child.borrow(), &mut screen, child_rect)
+            })
+            .collect();
+        self.handles.replace(handles);
+
+        let interaction = screen.backend.interact(rect, Sense::Hover);
+        ScreenHandle::new(interaction)
+    }
+
+    fn respond(&self, _handle: &ScreenHandle) {
+        for (child, handle) in self.children.iter().zip(self.handles.borrow().iter()) {
+            layout::respond(&*child.borrow(), handle);
+        }
+    }
+
+    fn semantics(&self, cx: &LayoutCx, rect: Rect) -> SemanticNode {
+        let children = self
+            .children
+            .iter()
+            .zip(self.arrange(cx, rect))
+            .map(|(child, child_rect)| layout::semantics(&*child.borrow(), cx, child_rect))
+            .collect();
+        SemanticNode {
+            test_id: self.test_id.clone(),
+            children,
+            ..SemanticNode::new(Role::Stack, rect)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StackData;
+    use crate::{
+        drawables::traits::Draw,
+        layout::{
+            tests::{cx, Content},
+            LayoutParams, Length, Rect, Size,
+        },
+        structures::CrossAlign,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn aligns_and_stretches_children() {
+        let small = Content(LayoutParams::fixed(20., 10.), Size::ZERO);
+        let wide = Content(
+            LayoutParams {
+                width: Length::MatchParent,
+                ..Default::default()
+            },
+            Size::new(5., 30.),
+        );
+        let stack = StackData {
+            children: vec![Rc::new(RefCell::new(small)), Rc::new(RefCell::new(wide))],
+            horizontal: CrossAlign::Center,
+            vertical: CrossAlign::End,
+            layout: LayoutParams::default(),
+            test_id: None,
+            gestures: Default::default(),
+            style: Default::default(),
+            handles: RefCell::new(vec![]),
+        };
+        let rects = stack.arrange(&cx(), Rect::new(0., 0., 100., 50.));
+        assert_eq!(rects[0], Rect::new(40., 40., 20., 10.));
+        assert_eq!(rects[1], Rect::new(0., 20., 100., 30.));
+        assert_eq!(
+            stack.measure(&cx(), Size::new(100., 50.)),
+            Size::new(100., 30.)
+        );
+    }
+}
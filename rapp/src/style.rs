@@ -0,0 +1,197 @@
+//! Modifiers that every drawable has, like padding, a background or a border.
+//!
+//! Drawables only keep their `Style`. The layout applies it the same way for all of them, see
+//! `layout::measure` and `layout::draw`: the margin is outside of the size the drawable is
+//! given, the padding inside of it.
+
+use crate::{
+    color::Color,
+    layout::{Rect, Size},
+};
+
+/// Space on each side of a drawable
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Insets {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl Insets {
+    pub const ZERO: Insets = Insets::all(0.);
+
+    pub const fn all(inset: f32) -> Self {
+        Self::symmetric(inset, inset)
+    }
+
+    pub const fn symmetric(horizontal: f32, vertical: f32) -> Self {
+        Self {
+            left: horizontal,
+            top: vertical,
+            right: horizontal,
+            bottom: vertical,
+        }
+    }
+
+    pub fn horizontal(&self) -> f32 {
+        self.left + self.right
+    }
+
+    pub fn vertical(&self) -> f32 {
+        self.top + self.bottom
+    }
+
+    /// Smaller by the insets, but never negative
+    pub fn shrink_size(&self, size: Size) -> Size {
+        Size::new(
+            (size.width - self.horizontal()).max(0.),
+            (size.height - self.vertical()).max(0.),
+        )
+    }
+
+    pub fn expand_size(&self, size: Size) -> Size {
+        Size::new(
+            size.width + self.horizontal(),
+            size.height + self.vertical(),
+        )
+    }
+
+    /// The part of `rect` within the insets
+    pub fn shrink_rect(&self, rect: Rect) -> Rect {
+        Rect::from_size(
+            rect.x + self.left,
+            rect.y + self.top,
+            self.shrink_size(rect.size()),
+        )
+    }
+}
+
+impl From<u16> for Insets {
+    fn from(inset: u16) -> Self {
+        Self::all(inset.into())
+    }
+}
+
+/// Horizontal and vertical insets
+impl From<(u16, u16)> for Insets {
+    fn from((horizontal, vertical): (u16, u16)) -> Self {
+        Self::symmetric(horizontal.into(), vertical.into())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Border {
+    pub width: f32,
+    pub color: Color,
+}
+
+/// A blurred copy of the box below it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shadow {
+    /// How far the shadow is moved to the right and down
+    pub offset: (f32, f32),
+    /// How far the blur extends outside of the box
+    pub blur: f32,
+    pub color: Color,
+}
+
+impl Default for Shadow {
+    fn default() -> Self {
+        Self {
+            offset: (0., 2.),
+            blur: 8.,
+            color: Color::rgba(0, 0, 0, 60),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Visibility {
+    #[default]
+    Visible,
+    /// Not drawn, but it keeps its place
+    Hidden,
+    /// Not drawn and without a place, as if it was not there
+    Gone,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Style {
+    pub padding: Insets,
+    pub margin: Insets,
+    pub background: Option<Color>,
+    pub border: Option<Border>,
+    /// Corner radius of the background and the border
+    pub rounding: f32,
+    /// 1 is opaque, 0 is invisible. It multiplies with the opacity of the parents.
+    pub opacity: f32,
+    pub shadow: Option<Shadow>,
+    pub visibility: Visibility,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            padding: Insets::ZERO,
+            margin: Insets::ZERO,
+            background: None,
+            border: None,
+            rounding: 0.,
+            opacity: 1.,
+            shadow: None,
+            visibility: Visibility::Visible,
+        }
+    }
+}
+
+impl Style {
+    /// Whether there is anything to paint behind the content
+    pub fn has_box(&self) -> bool {
+        self.background.is_some() || self.border.is_some() || self.shadow.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Visibility;
+    use crate::{
+        drawables::traits::{Sizing, Styled, TestId},
+        layout::{
+            tests::{TestBackend, TestRoute},
+            Rect,
+        },
+        navigation::Navigator,
+        screen::Screen,
+    };
+
+    #[test]
+    fn margin_outside_padding_inside() {
+        let mut backend = TestBackend;
+        let mut screen = Screen::new(&mut backend, Navigator::new(TestRoute));
+        screen
+            .column(|screen| {
+                screen.text("Hi").test_id("hi").padding((4, 2));
+                screen.placeholder().visible(false);
+                screen
+                    .placeholder()
+                    .test_id("gone")
+                    .visibility(Visibility::Gone);
+                screen.button("Ok").test_id("ok").margin(3);
+            })
+            .test_id("card")
+            .padding(10)
+            .margin(5)
+            .fill_width();
+
+        let tree = screen.semantics();
+        let card = tree.find_by_test_id("card").unwrap();
+        assert_eq!(card.bounds, Rect::new(5., 5., 190., 20. + 100. + 26. + 20.));
+        // Its own padding is within the size of the text
+        let hi = tree.find_by_test_id("hi").unwrap();
+        assert_eq!(hi.bounds, Rect::new(15., 15., 16. + 8., 16. + 4.));
+        assert!(tree.find_by_test_id("gone").is_none());
+        let ok = tree.find_by_test_id("ok").unwrap();
+        assert_eq!(ok.bounds, Rect::new(18., 15. + 20. + 100. + 3., 24., 20.));
+    }
+}
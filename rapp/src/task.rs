@@ -0,0 +1,434 @@
+//! Work that runs off the UI thread, like loading data.
+//!
+//! `spawn_blocking` hands the work to a small thread pool and `spawn` runs the future on a
+//! thread of its own, so slow futures like network calls do not hold up the pool. Every
+//! future that is waiting takes a thread, so spawn a few at a time. Both return a `Task`.
+//! Pages read its state in `Page::show`, and `Task::then` routes the result into app state.
+//! Results are taken in by `poll`, which the runner calls at the end of every frame. Finished
+//! work wakes the runner so it draws again.
+//!
+//! Tasks spawned while a page is shown, or by its callbacks, belong to the page. They are
+//! cancelled when the page disappears, see `TaskScope`. `Task::cancel` cancels one sooner, and
+//! tasks spawned in `detached` belong to no page.
+//!
+//! In manual mode, which the test driver uses, nothing runs until `run_pending` is called.
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+    time::Duration,
+};
+
+const POOL_SIZE: usize = 4;
+
+type Job = Box<dyn FnOnce() + Send>;
+type Then<T> = Box<dyn FnOnce(&T)>;
+
+thread_local! {
+    static EXECUTOR: RefCell<Executor> = RefCell::new(Executor::default());
+}
+
+/// Runs the future on a thread of its own
+pub fn spawn<T: Send + 'static>(future: impl Future<Output = T> + Send + 'static) -> Task<T> {
+    start(true, move |cancelled| block_on(future, cancelled))
+}
+
+/// Runs the work on the thread pool
+pub fn spawn_blocking<T: Send + 'static>(work: impl FnOnce() -> T + Send + 'static) -> Task<T> {
+    start(false, move |_| Some(work()))
+}
+
+/// Takes in the results of finished work and calls the `then` callbacks. Returns true if any
+/// task finished.
+pub fn poll() -> bool {
+    // Callbacks may spawn again
+    let pending = EXECUTOR.with(|executor| std::mem::take(&mut executor.borrow_mut().pending));
+    let mut finished = false;
+    let mut running = vec![];
+    for task in pending {
+        if task.poll() {
+            finished = true;
+        } else {
+            running.push(task);
+        }
+    }
+    EXECUTOR.with(|executor| {
+        let mut executor = executor.borrow_mut();
+        running.append(&mut executor.pending);
+        executor.pending = running;
+    });
+    finished
+}
+
+/// Runs `f` outside of any scope, so the tasks it spawns are not cancelled with a page, as for
+/// work whose result is shared by all pages
+pub fn detached<T>(f: impl FnOnce() -> T) -> T {
+    let outer = EXECUTOR.with(|executor| executor.borrow_mut().scope.take());
+    let result = f();
+    EXECUTOR.with(|executor| executor.borrow_mut().scope = outer);
+    result
+}
+
+/// Number of tasks that did not finish yet
+pub fn running() -> usize {
+    EXECUTOR.with(|executor| executor.borrow().pending.len())
+}
+
+/// In manual mode work waits for `run_pending` instead of running on the thread pool
+pub fn set_manual(manual: bool) {
+    EXECUTOR.with(|executor| executor.borrow_mut().manual = manual);
+}
+
+/// Runs the work that waits in manual mode on this thread, then polls. Returns the number of
+/// jobs that ran.
+pub fn run_pending() -> usize {
+    let mut ran = 0;
+    loop {
+        let jobs = EXECUTOR.with(|executor| std::mem::take(&mut executor.borrow_mut().queued));
+        if jobs.is_empty() {
+            break;
+        }
+        ran += jobs.len();
+        jobs.into_iter().for_each(|job| job());
+    }
+    poll();
+    ran
+}
+
+/// Called from the worker thread when work finished, for example to request a repaint
+pub fn set_waker(waker: impl Fn() + Send + Sync + 'static) {
+    EXECUTOR.with(|executor| executor.borrow_mut().waker = Some(Arc::new(waker)));
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TaskState<T> {
+    Running,
+    Done(T),
+    /// The work panicked, with the panic message
+    Failed(String),
+    Cancelled,
+}
+
+impl<T> TaskState<T> {
+    pub fn is_running(&self) -> bool {
+        matches!(self, TaskState::Running)
+    }
+
+    pub fn done(&self) -> Option<&T> {
+        match self {
+            TaskState::Done(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Handle to spawned work. Clones refer to the same task.
+pub struct Task<T>(Rc<TaskData<T>>);
+
+struct TaskData<T> {
+    state: RefCell<TaskState<T>>,
+    cancelled: Arc<AtomicBool>,
+    receiver: Receiver<Result<T, String>>,
+    then: RefCell<Vec<Then<T>>>,
+}
+
+impl<T: 'static> Task<T> {
+    pub fn state(&self) -> std::cell::Ref<'_, TaskState<T>> {
+        self.0.state.borrow()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.state().is_running()
+    }
+
+    /// Calls `then` on the UI thread with the result, when the work is done
+    pub fn then(self, then: impl FnOnce(&T) + 'static) -> Self {
+        if let TaskState::Done(value) = &*self.state() {
+            then(value);
+        } else {
+            self.0.then.borrow_mut().push(Box::new(then));
+        }
+        self
+    }
+
+    /// Drops the result. Work that did not start yet does not run, and futures are not polled
+    /// again.
+    pub fn cancel(&self) {
+        self.0.cancel();
+    }
+}
+
+impl<T> Clone for Task<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// Tasks that belong together, like those of a page. The tasks spawned in `enter` belong to
+/// the scope. Clones refer to the same scope.
+#[derive(Clone, Default)]
+pub struct TaskScope(Rc<RefCell<Vec<Rc<dyn Pending>>>>);
+
+impl TaskScope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `f`, with the tasks it spawns in this scope
+    pub fn enter<T>(&self, f: impl FnOnce() -> T) -> T {
+        let outer = EXECUTOR.with(|executor| executor.borrow_mut().scope.replace(self.clone()));
+        let result = f();
+        EXECUTOR.with(|executor| executor.borrow_mut().scope = outer);
+        result
+    }
+
+    /// Cancels the tasks of the scope that are still running
+    pub fn cancel(&self) {
+        for task in self.0.take() {
+            task.cancel();
+        }
+    }
+
+    fn add(&self, task: Rc<dyn Pending>) {
+        let mut tasks = self.0.borrow_mut();
+        tasks.retain(|task| task.is_running());
+        tasks.push(task);
+    }
+}
+
+// A task as the executor sees it
+trait Pending {
+    /// Returns true if the task finished
+    fn poll(&self) -> bool;
+
+    fn is_running(&self) -> bool;
+
+    fn cancel(&self);
+}
+
+impl<T> Pending for TaskData<T> {
+    fn is_running(&self) -> bool {
+        self.state.borrow().is_running()
+    }
+
+    fn cancel(&self) {
+        if self.is_running() {
+            self.cancelled.store(true, Ordering::Relaxed);
+            *self.state.borrow_mut() = TaskState::Cancelled;
+        }
+    }
+
+    fn poll(&self) -> bool {
+        if !self.state.borrow().is_running() {
+            return true;
+        }
+        let state = match self.receiver.try_recv() {
+            Ok(Ok(value)) => TaskState::Done(value),
+            Ok(Err(message)) => TaskState::Failed(message),
+            Err(mpsc::TryRecvError::Empty) => return false,
+            Err(mpsc::TryRecvError::Disconnected) => TaskState::Cancelled,
+        };
+        *self.state.borrow_mut() = state;
+        if let TaskState::Done(value) = &*self.state.borrow() {
+            for then in self.then.take() {
+                then(value);
+            }
+        }
+        true
+    }
+}
+
+#[derive(Default)]
+struct Executor {
+    pending: Vec<Rc<dyn Pending>>,
+    manual: bool,
+    // Work that waits for run_pending in manual mode
+    queued: Vec<Job>,
+    // Started on the first spawn
+    pool: Option<Sender<Job>>,
+    waker: Option<Arc<dyn Fn() + Send + Sync>>,
+    // The scope new tasks belong to
+    scope: Option<TaskScope>,
+}
+
+impl Executor {
+    fn submit(&mut self, job: Job, own_thread: bool) {
+        if self.manual {
+            self.queued.push(job);
+            return;
+        }
+        if own_thread {
+            thread::Builder::new()
+                .name("rapp-future".to_string())
+                .spawn(job)
+                .expect("failed to start a future thread");
+            return;
+        }
+        let pool = self.pool.get_or_insert_with(start_pool);
+        pool.send(job)
+            .expect("the thread pool runs while the executor exists");
+    }
+}
+
+fn start_pool() -> Sender<Job> {
+    let (sender, receiver) = mpsc::channel::<Job>();
+    let receiver = Arc::new(Mutex::new(receiver));
+    for index in 0..POOL_SIZE {
+        let receiver = receiver.clone();
+        thread::Builder::new()
+            .name(format!("rapp-task-{index}"))
+            .spawn(move || loop {
+                // Ends when the executor and its sender are dropped
+                let job = receiver.lock().unwrap().recv();
+                match job {
+                    Ok(job) => job(),
+                    Err(_) => break,
+                }
+            })
+            .expect("failed to start a task thread");
+    }
+    sender
+}
+
+fn start<T: Send + 'static>(
+    own_thread: bool,
+    work: impl FnOnce(&AtomicBool) -> Option<T> + Send + 'static,
+) -> Task<T> {
+    let (sender, receiver) = mpsc::channel();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let task = Task(Rc::new(TaskData {
+        state: RefCell::new(TaskState::Running),
+        cancelled: cancelled.clone(),
+        receiver,
+        then: RefCell::new(vec![]),
+    }));
+
+    EXECUTOR.with(|executor| {
+        let mut executor = executor.borrow_mut();
+        let waker = executor.waker.clone();
+        executor.pending.push(task.0.clone());
+        if let Some(scope) = &executor.scope {
+            scope.add(task.0.clone());
+        }
+        let job = Box::new(move || {
+            if cancelled.load(Ordering::Relaxed) {
+                return;
+            }
+            let result = match panic::catch_unwind(AssertUnwindSafe(|| work(&cancelled))) {
+                Ok(Some(value)) => Ok(value),
+                Ok(None) => return,
+                Err(panic) => Err(panic_message(panic)),
+            };
+            // The task may be gone
+            let _ = sender.send(result);
+            if let Some(waker) = waker {
+                waker();
+            }
+        });
+        executor.submit(job, own_thread);
+    });
+    task
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "the task panicked".to_string()
+    }
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Polls the future on this thread until it is ready, or None when it was cancelled
+fn block_on<F: Future>(future: F, cancelled: &AtomicBool) -> Option<F::Output> {
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return Some(output);
+        }
+        // Wakes up now and then to see if it was cancelled
+        thread::park_timeout(Duration::from_millis(10));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        poll, run_pending, running, set_manual, spawn, spawn_blocking, TaskState, POOL_SIZE,
+    };
+    use crate::store::Store;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn manual_mode() {
+        set_manual(true);
+        let loaded = Store::new(0);
+        let store = loaded.clone();
+        let task = spawn_blocking(|| 40 + 2).then(move |value| store.set(*value));
+        let future = spawn(async { "ready" });
+        let cancelled = spawn_blocking(|| unreachable!());
+        let failed = spawn_blocking(|| -> u32 { panic!("no network") });
+
+        assert!(task.is_running());
+        assert!(!poll());
+        cancelled.cancel();
+        assert_eq!(run_pending(), 4);
+        assert_eq!(*task.state(), TaskState::Done(42));
+        assert_eq!(loaded.get(), 42);
+        assert_eq!(future.state().done(), Some(&"ready"));
+        assert_eq!(*cancelled.state(), TaskState::Cancelled);
+        assert_eq!(*failed.state(), TaskState::Failed("no network".to_string()));
+        assert_eq!(running(), 0);
+    }
+
+    #[test]
+    fn runs_on_the_thread_pool() {
+        let task = spawn_blocking(|| std::thread::current().name().map(str::to_string));
+        let start = Instant::now();
+        while task.is_running() {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            poll();
+        }
+        let name = task.state().done().cloned().flatten().unwrap();
+        assert!(name.starts_with("rapp-task-"));
+    }
+
+    #[test]
+    fn futures_leave_the_thread_pool_free() {
+        let waiting: Vec<_> = (0..=POOL_SIZE)
+            .map(|_| spawn(std::future::pending::<()>()))
+            .collect();
+        let task = spawn_blocking(|| 42);
+        let start = Instant::now();
+        while task.is_running() {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            poll();
+        }
+        assert_eq!(task.state().done(), Some(&42));
+        waiting.iter().for_each(|task| task.cancel());
+    }
+}
@@ -0,0 +1,204 @@
+//! The colors, text styles and spacing the drawables use.
+//!
+//! The app picks a theme with `RustApp::theme`. Drawables take the theme of the screen they
+//! are queued on, so `Screen::with_theme` changes it for part of a page.
+
+use crate::{color::Color, drawables::text::FontWeight};
+
+/// Light or dark, as the user chose for the device
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Appearance {
+    #[default]
+    Light,
+    Dark,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    pub appearance: Appearance,
+    pub colors: Palette,
+    pub typography: Typography,
+    pub spacing: Spacing,
+}
+
+impl Theme {
+    pub fn light() -> Self {
+        Self {
+            appearance: Appearance::Light,
+            colors: Palette {
+                primary: Color::rgb(0, 150, 80),
+                on_primary: Color::WHITE,
+                accent: Color::GOLD,
+                background: Color::WHITE,
+                surface: Color::rgb(242, 242, 242),
+                text: Color::rgb(40, 40, 40),
+                text_weak: Color::rgb(120, 120, 120),
+                text_strong: Color::BLACK,
+                separator: Color::GRAY,
+                error: Color::rgb(200, 30, 30),
+            },
+            typography: Typography::default(),
+            spacing: Spacing::default(),
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            appearance: Appearance::Dark,
+            colors: Palette {
+                primary: Color::rgb(40, 180, 110),
+                on_primary: Color::BLACK,
+                accent: Color::rgb(230, 190, 0),
+                background: Color::rgb(20, 20, 20),
+                surface: Color::rgb(40, 40, 40),
+                text: Color::rgb(210, 210, 210),
+                text_weak: Color::rgb(140, 140, 140),
+                text_strong: Color::WHITE,
+                separator: Color::DARK_GRAY,
+                error: Color::rgb(240, 90, 90),
+            },
+            typography: Typography::default(),
+            spacing: Spacing::default(),
+        }
+    }
+
+    pub fn for_appearance(appearance: Appearance) -> Self {
+        match appearance {
+            Appearance::Light => Self::light(),
+            Appearance::Dark => Self::dark(),
+        }
+    }
+
+    /// The color of text with this weight
+    pub fn text_color(&self, weight: FontWeight) -> Color {
+        match weight {
+            FontWeight::Light => self.colors.text_weak,
+            FontWeight::Regular => self.colors.text,
+            FontWeight::Bold => self.colors.text_strong,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+/// The named colors of a theme
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Palette {
+    /// Buttons and other things that stand out
+    pub primary: Color,
+    /// Text on `primary`
+    pub on_primary: Color,
+    pub accent: Color,
+    /// Behind the page
+    pub background: Color,
+    /// Behind parts of the page that are set apart
+    pub surface: Color,
+    pub text: Color,
+    pub text_weak: Color,
+    pub text_strong: Color,
+    pub separator: Color,
+    pub error: Color,
+}
+
+/// The look of text, as the typography scale of a theme has it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Font {
+    pub size: f32,
+    pub weight: FontWeight,
+}
+
+/// Where text is in the typography scale
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextStyle {
+    Caption,
+    #[default]
+    Body,
+    Title,
+    Headline,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Typography {
+    pub caption: Font,
+    pub body: Font,
+    pub title: Font,
+    pub headline: Font,
+}
+
+impl Typography {
+    pub fn font(&self, style: TextStyle) -> Font {
+        match style {
+            TextStyle::Caption => self.caption,
+            TextStyle::Body => self.body,
+            TextStyle::Title => self.title,
+            TextStyle::Headline => self.headline,
+        }
+    }
+}
+
+impl Default for Typography {
+    fn default() -> Self {
+        let font = |size, weight| Font { size, weight };
+        Self {
+            caption: font(11., FontWeight::Light),
+            body: font(14., FontWeight::Regular),
+            title: font(18., FontWeight::Bold),
+            headline: font(24., FontWeight::Bold),
+        }
+    }
+}
+
+/// Space between and around drawables, from small to large
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spacing {
+    pub xs: u16,
+    pub s: u16,
+    pub m: u16,
+    pub l: u16,
+    pub xl: u16,
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Self {
+            xs: 2,
+            s: 4,
+            m: 8,
+            l: 16,
+            xl: 24,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Appearance, Theme};
+    use crate::{
+        layout::tests::{TestBackend, TestRoute},
+        navigation::Navigator,
+        screen::Screen,
+    };
+
+    #[test]
+    fn drawables_take_the_theme_of_the_screen() {
+        let mut backend = TestBackend;
+        let mut screen = Screen::new(&mut backend, Navigator::new(TestRoute));
+        screen.set_theme(Theme::dark());
+        let title = screen.text("Title");
+        let mut button = None;
+        let mut brand = Theme::light();
+        brand.colors.primary = brand.colors.accent;
+        screen.with_theme(brand.clone(), |screen| {
+            button = Some(screen.button("Buy"));
+        });
+        let separator = screen.separator();
+
+        assert_eq!(title.0.borrow().theme.appearance, Appearance::Dark);
+        assert_eq!(*button.unwrap().0.borrow().theme, brand);
+        assert_eq!(*separator.0.borrow().theme, Theme::dark());
+    }
+}
@@ -0,0 +1,208 @@
+//! Callbacks that run after a while or periodically, for countdowns, polling or clocks.
+//!
+//! Timers fire on the UI thread when the runner calls `fire_due`, at the end of a frame. The
+//! runner asks for a frame when the next timer is due. A page keeps its `Timer` in a field,
+//! as pages live between frames, and cancels it in `Page::on_disappear`.
+//!
+//! Tests use a virtual clock that only moves with `advance`.
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+// A repeating timer fires at most this often per call to fire_due, so a long pause does not
+// run it thousands of times
+const MAX_CATCH_UP: u32 = 100;
+
+thread_local! {
+    static SCHEDULER: RefCell<Scheduler> = RefCell::new(Scheduler::new());
+}
+
+/// Calls `callback` once, after `delay`
+pub fn after(delay: Duration, callback: impl FnMut() + 'static) -> Timer {
+    schedule(delay, None, callback)
+}
+
+/// Calls `callback` every `interval`, starting one interval from now
+pub fn every(interval: Duration, callback: impl FnMut() + 'static) -> Timer {
+    assert!(!interval.is_zero(), "a repeating timer needs an interval");
+    schedule(interval, Some(interval), callback)
+}
+
+/// Time since the clock started
+pub fn now() -> Duration {
+    SCHEDULER.with(|scheduler| scheduler.borrow().clock.now())
+}
+
+/// Runs the callbacks of the timers that are due. Returns true if any fired.
+pub fn fire_due() -> bool {
+    let now = now();
+    let mut fired = false;
+    // Callbacks may start and cancel timers, so the due ones are taken out first
+    let timers = SCHEDULER.with(|scheduler| std::mem::take(&mut scheduler.borrow_mut().timers));
+    let mut kept = vec![];
+    for timer in timers {
+        let mut times = 0;
+        while timer.active.get() && timer.deadline.get() <= now && times < MAX_CATCH_UP {
+            match timer.interval {
+                Some(interval) => timer.deadline.set(timer.deadline.get() + interval),
+                None => timer.active.set(false),
+            }
+            (timer.callback.borrow_mut())();
+            times += 1;
+            fired = true;
+        }
+        if timer.active.get() {
+            // Behind by more than the catch up, it continues from now
+            if timer.deadline.get() <= now {
+                timer.deadline.set(now + timer.interval.unwrap_or_default());
+            }
+            kept.push(timer);
+        }
+    }
+    SCHEDULER.with(|scheduler| {
+        let mut scheduler = scheduler.borrow_mut();
+        kept.append(&mut scheduler.timers);
+        scheduler.timers = kept;
+    });
+    fired
+}
+
+/// Time until the next timer is due, or None when no timer runs
+pub fn next_due() -> Option<Duration> {
+    let now = now();
+    SCHEDULER.with(|scheduler| {
+        let scheduler = scheduler.borrow();
+        scheduler
+            .timers
+            .iter()
+            .filter(|timer| timer.active.get())
+            .map(|timer| timer.deadline.get().saturating_sub(now))
+            .min()
+    })
+}
+
+/// Stops the clock. It then only moves with `advance`.
+pub fn use_virtual_clock() {
+    SCHEDULER.with(|scheduler| {
+        let mut scheduler = scheduler.borrow_mut();
+        let now = scheduler.clock.now();
+        scheduler.clock = Clock::Virtual(now);
+    });
+}
+
+/// Moves the virtual clock forward and fires the timers that became due. Returns true if any
+/// fired.
+///
+/// Panics when the clock is not virtual.
+pub fn advance(by: Duration) -> bool {
+    SCHEDULER.with(|scheduler| match &mut scheduler.borrow_mut().clock {
+        Clock::Virtual(now) => *now += by,
+        Clock::Real(_) => panic!("only a virtual clock can be advanced"),
+    });
+    fire_due()
+}
+
+/// Handle to a scheduled callback. Clones refer to the same timer.
+#[derive(Clone)]
+pub struct Timer(Rc<TimerData>);
+
+struct TimerData {
+    deadline: Cell<Duration>,
+    interval: Option<Duration>,
+    active: Cell<bool>,
+    callback: RefCell<Box<dyn FnMut()>>,
+}
+
+impl Timer {
+    /// Whether it will fire again
+    pub fn is_active(&self) -> bool {
+        self.0.active.get()
+    }
+
+    pub fn cancel(&self) {
+        self.0.active.set(false);
+    }
+}
+
+enum Clock {
+    Real(Instant),
+    Virtual(Duration),
+}
+
+impl Clock {
+    fn now(&self) -> Duration {
+        match self {
+            Clock::Real(start) => start.elapsed(),
+            Clock::Virtual(now) => *now,
+        }
+    }
+}
+
+struct Scheduler {
+    clock: Clock,
+    timers: Vec<Rc<TimerData>>,
+}
+
+impl Scheduler {
+    fn new() -> Self {
+        Self {
+            clock: Clock::Real(Instant::now()),
+            timers: vec![],
+        }
+    }
+}
+
+fn schedule(
+    delay: Duration,
+    interval: Option<Duration>,
+    callback: impl FnMut() + 'static,
+) -> Timer {
+    let data = Rc::new(TimerData {
+        deadline: Cell::new(now() + delay),
+        interval,
+        active: Cell::new(true),
+        callback: RefCell::new(Box::new(callback)),
+    });
+    SCHEDULER.with(|scheduler| scheduler.borrow_mut().timers.push(data.clone()));
+    Timer(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{advance, after, every, fire_due, next_due, use_virtual_clock};
+    use std::{cell::Cell, rc::Rc, time::Duration};
+
+    #[test]
+    fn virtual_clock() {
+        use_virtual_clock();
+        let ticks = Rc::new(Cell::new(0));
+        let done = Rc::new(Cell::new(false));
+        let ticker = {
+            let ticks = ticks.clone();
+            every(Duration::from_secs(1), move || ticks.set(ticks.get() + 1))
+        };
+        let countdown = {
+            let done = done.clone();
+            after(Duration::from_millis(2500), move || done.set(true))
+        };
+        assert_eq!(next_due(), Some(Duration::from_secs(1)));
+        assert!(!fire_due());
+
+        assert!(advance(Duration::from_millis(2400)));
+        assert_eq!(ticks.get(), 2);
+        assert!(!done.get());
+        assert_eq!(next_due(), Some(Duration::from_millis(100)));
+
+        advance(Duration::from_millis(100));
+        assert!(done.get());
+        assert!(!countdown.is_active());
+
+        ticker.cancel();
+        assert!(!advance(Duration::from_secs(10)));
+        assert_eq!(ticks.get(), 2);
+        assert_eq!(next_due(), None);
+    }
+}
@@ -0,0 +1,619 @@
+use crate::{render_to_image, Device, RgbaImage};
+use egui::{
+    pos2, vec2, CentralPanel, Context, Event, Frame, Key, Modifiers, PointerButton, Pos2, RawInput,
+};
+use rapp::{
+    backend::egui::{EguiBackend, RADIANS_PER_SCROLL},
+    lifecycle::Lifecycle,
+    navigation::Navigator,
+    pages::PageCache,
+    screen::Screen,
+    semantics::{Role, SemanticNode, SemanticTree},
+    task,
+    theme::Appearance,
+    timer, RustApp,
+};
+use std::time::Duration;
+
+/// Seconds between two frames
+const FRAME_SECONDS: f64 = 1. / 60.;
+
+/// How to find a drawable on the current page
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Find<'a> {
+    /// A drawable that shows exactly this text
+    Text(&'a str),
+    TestId(&'a str),
+}
+
+/// Runs an app without a window and acts like a user: taps, types, scrolls and pinches.
+///
+/// Every action runs the frames it needs. Callbacks run at the end of a frame and navigation
+/// requests are applied after it, as in the runner.
+pub struct Driver<A: RustApp> {
+    app: A,
+    navigator: Navigator<A::Route>,
+    pages: PageCache<A::Route>,
+    lifecycle: Lifecycle<A::Route>,
+    device: Device,
+    ctx: Context,
+    time: f64,
+    pointer: Pos2,
+    events: Vec<Event>,
+    // The keys held with the queued input
+    modifiers: Modifiers,
+    semantics: SemanticTree,
+}
+
+impl<A: RustApp> Driver<A> {
+    /// Starts the app on its home page, on a phone.
+    ///
+    /// Spawned tasks wait for `run_tasks` and timers use a virtual clock that moves with
+    /// `advance`, so tests decide when they finish.
+    pub fn new(app: A) -> Self {
+        Self::with_device(app, Device::PHONE)
+    }
+
+    pub fn with_device(mut app: A, device: Device) -> Self {
+        task::set_manual(true);
+        timer::use_virtual_clock();
+        app.start();
+        let navigator = Navigator::new(app.home());
+        let mut driver = Self {
+            app,
+            navigator,
+            pages: PageCache::new(),
+            lifecycle: Lifecycle::new(),
+            device,
+            ctx: Context::default(),
+            time: 0.,
+            pointer: Pos2::ZERO,
+            events: vec![],
+            modifiers: Modifiers::NONE,
+            semantics: SemanticTree {
+                root: SemanticNode::new(Role::Column, Default::default()),
+            },
+        };
+        driver
+            .lifecycle
+            .navigated(&mut driver.app, &mut driver.pages, &driver.navigator);
+        driver.frame();
+        driver
+    }
+
+    pub fn app(&self) -> &A {
+        &self.app
+    }
+
+    pub fn app_mut(&mut self) -> &mut A {
+        &mut self.app
+    }
+
+    pub fn navigator(&self) -> &Navigator<A::Route> {
+        &self.navigator
+    }
+
+    /// The route of the page that is shown
+    pub fn current_page(&self) -> A::Route {
+        self.navigator.current()
+    }
+
+    /// The semantic tree of the last frame
+    pub fn semantics(&self) -> &SemanticTree {
+        &self.semantics
+    }
+
+    pub fn find(&self, find: Find) -> Option<&SemanticNode> {
+        match find {
+            Find::Text(text) => self.semantics.find_by_text(text),
+            Find::TestId(test_id) => self.semantics.find_by_test_id(test_id),
+        }
+    }
+
+    /// Runs a frame with the input that was queued, and one more to show what the callbacks
+    /// changed
+    pub fn frame(&mut self) {
+        for _ in 0..2 {
+            self.run_frame();
+            self.app.frame_end();
+            task::poll();
+            timer::fire_due();
+            if self.navigator.apply() {
+                self.lifecycle
+                    .navigated(&mut self.app, &mut self.pages, &self.navigator);
+            }
+        }
+    }
+
+    /// Runs the work of the spawned tasks, then a frame to show the results
+    pub fn run_tasks(&mut self) {
+        task::run_pending();
+        self.frame();
+    }
+
+    /// Sends the app to the background, as when the user switches to another app
+    pub fn pause(&mut self) {
+        self.lifecycle.set_paused(&mut self.app, true);
+        self.frame();
+    }
+
+    /// Brings the app back from the background
+    pub fn resume(&mut self) {
+        self.lifecycle.set_paused(&mut self.app, false);
+        self.frame();
+    }
+
+    pub fn paused(&self) -> bool {
+        self.lifecycle.paused()
+    }
+
+    /// Lets time pass, then runs a frame
+    pub fn advance(&mut self, seconds: f64) {
+        self.time += seconds;
+        timer::advance(Duration::from_secs_f64(seconds));
+        self.frame();
+    }
+
+    /// Presses and releases the pointer on the middle of the drawable
+    #[track_caller]
+    pub fn tap(&mut self, find: Find) {
+        self.press(find);
+        self.release();
+    }
+
+    /// Holds the pointer on the drawable until it counts as a long press
+    #[track_caller]
+    pub fn long_press(&mut self, find: Find) {
+        self.press(find);
+        self.advance(1.);
+        self.release();
+    }
+
+    /// Taps the drawable twice, quick enough for a double tap
+    #[track_caller]
+    pub fn double_tap(&mut self, find: Find) {
+        self.tap(find);
+        self.tap(find);
+    }
+
+    /// Pinches the drawable by `scale`, above 1 to zoom in. Like ctrl+scroll in the viewer.
+    #[track_caller]
+    pub fn pinch(&mut self, find: Find, scale: f32) {
+        self.move_to(find);
+        self.events.push(Event::Zoom(scale));
+        self.frame();
+    }
+
+    /// Turns two fingers on the drawable by `radians`, clockwise. Like alt+scroll in the
+    /// viewer.
+    #[track_caller]
+    pub fn rotate(&mut self, find: Find, radians: f32) {
+        self.move_to(find);
+        let delta = vec2(0., radians / RADIANS_PER_SCROLL);
+        self.events.push(Event::Scroll(delta));
+        self.modifiers = Modifiers::ALT;
+        self.frame();
+        self.modifiers = Modifiers::NONE;
+    }
+
+    /// Sends text to the input that has the focus, for example after a tap on it
+    pub fn type_text(&mut self, text: &str) {
+        self.events.push(Event::Text(text.to_string()));
+        self.frame();
+    }
+
+    pub fn press_key(&mut self, key: Key) {
+        for pressed in [true, false] {
+            self.events.push(Event::Key {
+                key,
+                pressed,
+                repeat: false,
+                modifiers: Default::default(),
+            });
+        }
+        self.frame();
+    }
+
+    /// Scrolls the content under the drawable by `delta` points. Negative `y` moves the
+    /// content up, as when swiping up.
+    #[track_caller]
+    pub fn scroll(&mut self, find: Find, delta: (f32, f32)) {
+        self.move_to(find);
+        self.events.push(Event::Scroll(vec2(delta.0, delta.1)));
+        self.frame();
+    }
+
+    /// Presses on the middle of the drawable, moves the pointer by `delta` points in a few
+    /// steps and releases it, as when pulling a list down or swiping a row
+    #[track_caller]
+    pub fn drag(&mut self, find: Find, delta: (f32, f32)) {
+        const STEPS: u8 = 5;
+        self.press(find);
+        let step = vec2(delta.0, delta.1) / f32::from(STEPS);
+        for _ in 0..STEPS {
+            self.pointer += step;
+            self.events.push(Event::PointerMoved(self.pointer));
+            self.frame();
+        }
+        self.release();
+    }
+
+    /// Renders the current page, with the state it has, in the light theme of the app
+    pub fn screenshot(&mut self) -> RgbaImage {
+        let route = self.current_page();
+        let theme = self.app.theme(Appearance::Light);
+        let page = self.pages.get(&mut self.app, &route);
+        render_to_image(page, self.navigator.clone(), &theme, self.device)
+    }
+
+    #[track_caller]
+    fn move_to(&mut self, find: Find) {
+        let node = self
+            .find(find)
+            .unwrap_or_else(|| panic!("no drawable for {find:?} on {:?}", self.current_page()));
+        let (x, y) = (
+            node.bounds.x + node.bounds.width / 2.,
+            node.bounds.y + node.bounds.height / 2.,
+        );
+        self.pointer = pos2(x, y);
+        self.events.push(Event::PointerMoved(self.pointer));
+    }
+
+    #[track_caller]
+    fn press(&mut self, find: Find) {
+        self.move_to(find);
+        self.pointer_button(true);
+    }
+
+    fn release(&mut self) {
+        self.pointer_button(false);
+    }
+
+    fn pointer_button(&mut self, pressed: bool) {
+        self.events.push(Event::PointerButton {
+            pos: self.pointer,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        });
+        self.frame();
+    }
+
+    fn run_frame(&mut self) {
+        self.time += FRAME_SECONDS;
+        let input = RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                Pos2::ZERO,
+                vec2(self.device.size.0, self.device.size.1),
+            )),
+            pixels_per_point: Some(self.device.pixels_per_point),
+            time: Some(self.time),
+            events: std::mem::take(&mut self.events),
+            modifiers: self.modifiers,
+            ..Default::default()
+        };
+
+        let stack = self.navigator.stack();
+        self.lifecycle
+            .retain(&mut self.app, &mut self.pages, &stack);
+        self.lifecycle
+            .navigated(&mut self.app, &mut self.pages, &self.navigator);
+        let route = self.navigator.current();
+        let tasks = self.pages.tasks(&mut self.app, &route);
+        let page = self.pages.get(&mut self.app, &route);
+        let navigator = self.navigator.clone();
+        let theme = self.app.theme(Appearance::Light);
+        let mut semantics = None;
+        let ctx = &self.ctx;
+        tasks.enter(|| {
+            let _ = ctx.run(input, |ctx| {
+                CentralPanel::default()
+                    .frame(Frame::none())
+                    .show(ctx, |ui| {
+                        let mut backend = EguiBackend::new(ui);
+                        let mut screen = Screen::new(&mut backend, navigator);
+                        screen.set_theme(theme);
+                        page.show(&mut screen);
+                        semantics = Some(screen.semantics());
+                        screen.draw();
+                    });
+            });
+        });
+        if let Some(semantics) = semantics {
+            self.semantics = semantics;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Driver, Find};
+    use egui::Key;
+    use rapp::{
+        assets::{self, ImageSource, ImageStatus},
+        drawables::traits::{Gestures, Sizing, TestId},
+        gestures::{Gesture, GestureKind, PanPhase, SwipeDirection},
+        navigation::Route,
+        screen::Screen,
+        store::Store,
+        task::{self, Task, TaskState},
+        Page, PageFn, RustApp,
+    };
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum TestRoute {
+        Counter,
+        Done,
+        Canvas,
+        Loading,
+        Pin,
+    }
+
+    impl Route for TestRoute {}
+
+    const DOT: ImageSource = ImageSource::Embedded {
+        path: "dot.svg",
+        bytes: br#"<svg xmlns="http://www.w3.org/2000/svg" width="4" height="4"/>"#,
+    };
+
+    #[derive(Default)]
+    struct TestApp {
+        count: Rc<Cell<u32>>,
+        gestures: Rc<RefCell<Vec<Gesture>>>,
+        // What the loading page started, and whether it finished
+        loading: Rc<RefCell<Option<Task<u32>>>>,
+        loaded: Rc<Cell<bool>>,
+        pin: Store<String>,
+    }
+
+    impl RustApp for TestApp {
+        type Route = TestRoute;
+
+        fn home(&self) -> TestRoute {
+            TestRoute::Counter
+        }
+
+        fn routes(&self) -> Vec<TestRoute> {
+            vec![
+                TestRoute::Counter,
+                TestRoute::Done,
+                TestRoute::Canvas,
+                TestRoute::Loading,
+                TestRoute::Pin,
+            ]
+        }
+
+        fn page(&mut self, route: &TestRoute) -> PageFn {
+            match route {
+                TestRoute::Counter => Box::new(CounterPage(self.count.clone())),
+                TestRoute::Done => Box::new(DonePage),
+                TestRoute::Canvas => Box::new(CanvasPage(self.gestures.clone())),
+                TestRoute::Loading => Box::new(LoadingPage {
+                    task: self.loading.clone(),
+                    loaded: self.loaded.clone(),
+                }),
+                TestRoute::Pin => Box::new(PinPage(self.pin.clone())),
+            }
+        }
+
+        fn start(&mut self) {}
+
+        fn new() -> Self {
+            Self::default()
+        }
+    }
+
+    struct CounterPage(Rc<Cell<u32>>);
+
+    impl Page for CounterPage {
+        fn name(&self) -> String {
+            "CounterPage".to_string()
+        }
+
+        fn show(&mut self, screen: &mut Screen) {
+            let count = self.0.clone();
+            screen.text(format!("Count {}", count.get()));
+            screen
+                .button("Add")
+                .test_id("add")
+                .on_click(move || count.set(count.get() + 1));
+            let navigator = screen.navigator::<TestRoute>();
+            screen
+                .button("Done")
+                .on_long_press(move || navigator.push(TestRoute::Done));
+        }
+    }
+
+    struct DonePage;
+
+    impl Page for DonePage {
+        fn name(&self) -> String {
+            "DonePage".to_string()
+        }
+
+        fn show(&mut self, screen: &mut Screen) {
+            screen.text("All done");
+        }
+    }
+
+    struct CanvasPage(Rc<RefCell<Vec<Gesture>>>);
+
+    impl Page for CanvasPage {
+        fn name(&self) -> String {
+            "CanvasPage".to_string()
+        }
+
+        fn show(&mut self, screen: &mut Screen) {
+            let kinds = [
+                GestureKind::LongPress,
+                GestureKind::DoubleTap,
+                GestureKind::Swipe,
+                GestureKind::Pan,
+                GestureKind::Pinch,
+            ];
+            let canvas = screen
+                .placeholder()
+                .width(200)
+                .height(200)
+                .test_id("canvas");
+            kinds.into_iter().fold(canvas, |canvas, kind| {
+                let gestures = self.0.clone();
+                canvas.on_gesture(kind, move |gesture| gestures.borrow_mut().push(*gesture))
+            });
+            screen.text("Canvas").test_id("canvas_label");
+        }
+    }
+
+    struct LoadingPage {
+        task: Rc<RefCell<Option<Task<u32>>>>,
+        loaded: Rc<Cell<bool>>,
+    }
+
+    impl Page for LoadingPage {
+        fn name(&self) -> String {
+            "LoadingPage".to_string()
+        }
+
+        fn show(&mut self, screen: &mut Screen) {
+            let mut task = self.task.borrow_mut();
+            let loaded = self.loaded.clone();
+            task.get_or_insert_with(|| task::spawn_blocking(|| 42).then(move |_| loaded.set(true)));
+            screen.text("Loading");
+            screen.image(DOT);
+        }
+    }
+
+    struct PinPage(Store<String>);
+
+    impl Page for PinPage {
+        fn name(&self) -> String {
+            "PinPage".to_string()
+        }
+
+        fn show(&mut self, screen: &mut Screen) {
+            screen.text_field(&self.0).max_length(4).test_id("pin");
+        }
+    }
+
+    #[test]
+    fn tap_and_long_press() {
+        let mut driver = Driver::new(TestApp::new());
+        assert!(driver.find(Find::Text("Count 0")).is_some());
+
+        driver.tap(Find::TestId("add"));
+        driver.tap(Find::TestId("add"));
+        assert_eq!(driver.app().count.get(), 2);
+        assert!(driver.find(Find::Text("Count 2")).is_some());
+
+        // A tap is not enough
+        driver.tap(Find::Text("Done"));
+        assert_eq!(driver.current_page(), TestRoute::Counter);
+        driver.long_press(Find::Text("Done"));
+        assert_eq!(driver.current_page(), TestRoute::Done);
+        assert!(driver.find(Find::Text("All done")).is_some());
+    }
+
+    #[test]
+    fn gestures() {
+        let mut driver = Driver::new(TestApp::new());
+        driver.navigator().push(TestRoute::Canvas);
+        driver.frame();
+        let canvas = Find::TestId("canvas");
+        let taken = |driver: &mut Driver<TestApp>| driver.app().gestures.take();
+
+        driver.double_tap(canvas);
+        assert_eq!(
+            taken(&mut driver),
+            [Gesture::DoubleTap { pos: (100., 100.) }]
+        );
+        driver.long_press(canvas);
+        assert_eq!(
+            taken(&mut driver),
+            [Gesture::LongPress { pos: (100., 100.) }]
+        );
+
+        driver.drag(canvas, (120., 0.));
+        let gestures = taken(&mut driver);
+        let phases: Vec<_> = gestures
+            .iter()
+            .filter_map(|gesture| match gesture {
+                Gesture::Pan { phase, .. } => Some(*phase),
+                _ => None,
+            })
+            .collect();
+        let (started, moved, ended) = (PanPhase::Started, PanPhase::Moved, PanPhase::Ended);
+        assert_eq!(phases, [started, moved, moved, moved, moved, ended]);
+        assert!(matches!(
+            gestures.last(),
+            Some(Gesture::Swipe {
+                direction: SwipeDirection::Right,
+                ..
+            })
+        ));
+
+        // Ignored outside of it
+        driver.pinch(Find::TestId("canvas_label"), 2.);
+        assert_eq!(taken(&mut driver), []);
+        driver.pinch(canvas, 2.);
+        driver.rotate(canvas, 0.5);
+        let gestures = taken(&mut driver);
+        let Gesture::Pinch { scale, .. } = gestures[0] else {
+            panic!("no pinch in {gestures:?}");
+        };
+        assert_eq!(scale, 2.);
+        let Gesture::Pinch { rotation, .. } = gestures[1] else {
+            panic!("no rotation in {gestures:?}");
+        };
+        assert!((rotation - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn tasks_are_cancelled_when_their_page_disappears() {
+        let mut driver = Driver::new(TestApp::new());
+        driver.navigator().push(TestRoute::Loading);
+        driver.frame();
+        let task = driver.app().loading.borrow().clone().unwrap();
+        assert!(task.is_running());
+
+        driver.navigator().pop();
+        driver.frame();
+        driver.run_tasks();
+        assert_eq!(*task.state(), TaskState::Cancelled);
+        assert!(!driver.app().loaded.get());
+    }
+
+    #[test]
+    fn images_load_after_their_page_disappears() {
+        let mut driver = Driver::new(TestApp::new());
+        driver.navigator().push(TestRoute::Loading);
+        driver.frame();
+        assert_eq!(assets::load(&DOT), ImageStatus::Loading);
+
+        driver.navigator().pop();
+        driver.frame();
+        driver.run_tasks();
+        assert!(matches!(assets::load(&DOT), ImageStatus::Ready(_)));
+    }
+
+    #[test]
+    fn typing_past_the_max_length() {
+        let mut driver = Driver::new(TestApp::new());
+        driver.navigator().push(TestRoute::Pin);
+        driver.frame();
+        driver.tap(Find::TestId("pin"));
+        driver.type_text("123456");
+        assert_eq!(driver.app().pin.get(), "1234");
+
+        // The cursor stays at the end of the text, not after what did not fit
+        driver.press_key(Key::ArrowLeft);
+        driver.press_key(Key::ArrowLeft);
+        driver.press_key(Key::Backspace);
+        assert_eq!(driver.app().pin.get(), "134");
+        driver.type_text("78");
+        assert_eq!(driver.app().pin.get(), "1734");
+    }
+}
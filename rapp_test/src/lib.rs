@@ -0,0 +1,50 @@
+//! Helpers to test rapp apps without a window
+
+mod driver;
+mod snapshot;
+
+pub use driver::{Driver, Find};
+pub use runner::{page_semantics, render_page_to_image, render_to_image, Device, RgbaImage};
+pub use snapshot::{assert_image_snapshot, snapshot_page, Tolerance, BLESS_VAR};
+
+use rapp::navigation::Route;
+
+/// Route for pages that do not navigate
+#[derive(Clone, Debug, PartialEq)]
+pub struct NoRoute;
+
+impl Route for NoRoute {}
+
+/// Renders a page and compares it with the reference image `tests/snapshots/<name>.png` of
+/// the crate that runs the test.
+///
+/// Pass the route of the app when the page uses the navigator, and the theme of the app when
+/// it is not the default one:
+///
+/// ```ignore
+/// assert_page_snapshot!("home", HomePage);
+/// assert_page_snapshot!("details", DetailsPage { id: 1 }, AppRoute::Details { id: 1 });
+/// assert_page_snapshot!("dark", HomePage, AppRoute::Home, app.theme(Appearance::Dark));
+/// ```
+///
+/// A missing reference is recorded. Set `RAPP_BLESS=1` to record all of them again.
+#[macro_export]
+macro_rules! assert_page_snapshot {
+    ($name:expr, $page:expr) => {
+        $crate::assert_page_snapshot!($name, $page, $crate::NoRoute)
+    };
+    ($name:expr, $page:expr, $route:expr) => {
+        $crate::assert_page_snapshot!($name, $page, $route, ::rapp::theme::Theme::default())
+    };
+    ($name:expr, $page:expr, $route:expr, $theme:expr) => {{
+        let mut page = $page;
+        $crate::snapshot_page(
+            $name,
+            &mut page,
+            $route,
+            &$theme,
+            $crate::Device::PHONE,
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots"),
+        );
+    }};
+}
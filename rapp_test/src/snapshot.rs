@@ -0,0 +1,207 @@
+use crate::{render_to_image, Device, RgbaImage};
+use image::Rgba;
+use rapp::{
+    navigation::{Navigator, Route},
+    theme::Theme,
+    Page,
+};
+use std::{env, fs, path::Path};
+
+/// Environment variable that records the references again instead of comparing
+pub const BLESS_VAR: &str = "RAPP_BLESS";
+
+/// How much an image may differ from its reference
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerance {
+    /// Largest difference of a color channel for pixels that count as equal
+    pub channel: u8,
+    /// Part of the pixels, from 0 to 1, that may differ
+    pub pixels: f32,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            channel: 8,
+            pixels: 0.001,
+        }
+    }
+}
+
+/// Renders `page` in `theme` on `device` and compares it with `<dir>/<name>.png`, see
+/// [`assert_image_snapshot`]
+#[track_caller]
+pub fn snapshot_page<R: Route>(
+    name: &str,
+    page: &mut dyn Page,
+    route: R,
+    theme: &Theme,
+    device: Device,
+    dir: impl AsRef<Path>,
+) {
+    let image = render_to_image(page, Navigator::new(route), theme, device);
+    assert_image_snapshot(name, &image, dir, Tolerance::default());
+}
+
+/// Compares `image` with the reference `<dir>/<name>.png`.
+///
+/// A missing reference is recorded, and so is every reference when `RAPP_BLESS` is set.
+/// When the images differ, the actual and expected image and a diff are written to
+/// `<dir>/failures` and the test fails.
+#[track_caller]
+pub fn assert_image_snapshot(
+    name: &str,
+    image: &RgbaImage,
+    dir: impl AsRef<Path>,
+    tolerance: Tolerance,
+) {
+    let bless = env::var_os(BLESS_VAR).map_or(false, |value| value != "0");
+    check(name, image, dir.as_ref(), tolerance, bless);
+}
+
+#[track_caller]
+fn check(name: &str, image: &RgbaImage, dir: &Path, tolerance: Tolerance, bless: bool) {
+    let reference = dir.join(format!("{name}.png"));
+    let failures = dir.join("failures");
+    let failure = |kind: &str| failures.join(format!("{name}.{kind}.png"));
+    // Whatever failed before is solved
+    let remove_failure = || {
+        for kind in ["actual", "expected", "diff"] {
+            let _ = fs::remove_file(failure(kind));
+        }
+    };
+
+    if bless || !reference.exists() {
+        fs::create_dir_all(dir).unwrap_or_else(|e| panic!("cannot create {dir:?}: {e}"));
+        save(image, &reference);
+        remove_failure();
+        eprintln!("recorded snapshot {reference:?}");
+        return;
+    }
+
+    let expected = image::open(&reference)
+        .unwrap_or_else(|e| panic!("cannot read snapshot {reference:?}: {e}"))
+        .into_rgba8();
+    let comparison = compare(image, &expected, tolerance.channel);
+    let allowed = (tolerance.pixels * comparison.total as f32).floor() as usize;
+    if comparison.different <= allowed {
+        remove_failure();
+        return;
+    }
+
+    fs::create_dir_all(&failures).unwrap_or_else(|e| panic!("cannot create {failures:?}: {e}"));
+    save(image, &failure("actual"));
+    save(&expected, &failure("expected"));
+    save(&comparison.diff, &failure("diff"));
+    panic!(
+        "snapshot {name:?} differs from {reference:?}: {} of {} pixels ({}x{} against {}x{}). \
+         See {failures:?}, or set {BLESS_VAR}=1 to accept the change.",
+        comparison.different,
+        comparison.total,
+        image.width(),
+        image.height(),
+        expected.width(),
+        expected.height(),
+    );
+}
+
+#[track_caller]
+fn save(image: &RgbaImage, path: &Path) {
+    image
+        .save(path)
+        .unwrap_or_else(|e| panic!("cannot write {path:?}: {e}"));
+}
+
+struct Comparison {
+    different: usize,
+    total: usize,
+    /// The differing pixels in red over a faded copy of the actual image
+    diff: RgbaImage,
+}
+
+fn compare(actual: &RgbaImage, expected: &RgbaImage, channel: u8) -> Comparison {
+    // Pixels outside of one of the images count as different
+    let width = actual.width().max(expected.width());
+    let height = actual.height().max(expected.height());
+
+    let mut different = 0;
+    let diff = RgbaImage::from_fn(width, height, |x, y| {
+        let pixels = (
+            actual.get_pixel_checked(x, y),
+            expected.get_pixel_checked(x, y),
+        );
+        match pixels {
+            (Some(a), Some(e)) if a.0.iter().zip(e.0).all(|(a, e)| a.abs_diff(e) <= channel) => {
+                let [r, g, b, _] = a.0;
+                let gray = ((r as u16 + g as u16 + b as u16) / 3) as u8;
+                Rgba([gray / 3, gray / 3, gray / 3, 255])
+            }
+            _ => {
+                different += 1;
+                Rgba([255, 0, 0, 255])
+            }
+        }
+    });
+
+    Comparison {
+        different,
+        total: (width * height) as usize,
+        diff,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, compare, Tolerance};
+    use crate::RgbaImage;
+    use image::Rgba;
+    use std::{env, fs, panic};
+
+    fn image(color: [u8; 4]) -> RgbaImage {
+        RgbaImage::from_pixel(10, 10, Rgba(color))
+    }
+
+    #[test]
+    fn compare_with_tolerance() {
+        let base = image([100, 100, 100, 255]);
+        assert_eq!(compare(&base, &image([105, 95, 100, 255]), 8).different, 0);
+
+        let mut changed = base.clone();
+        changed.put_pixel(3, 4, Rgba([0, 0, 0, 255]));
+        let comparison = compare(&changed, &base, 8);
+        assert_eq!(comparison.different, 1);
+        assert_eq!(comparison.diff.get_pixel(3, 4).0, [255, 0, 0, 255]);
+
+        // Sizes that do not match
+        let larger = RgbaImage::from_pixel(10, 12, Rgba([100, 100, 100, 255]));
+        assert_eq!(compare(&larger, &base, 8).different, 20);
+    }
+
+    #[test]
+    fn records_compares_and_reports() {
+        let dir = env::temp_dir().join(format!("rapp_test_snapshot_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let blue = image([0, 0, 255, 255]);
+        let red = image([255, 0, 0, 255]);
+        let tolerance = Tolerance::default();
+
+        // Recorded the first time, equal the second time
+        check("square", &blue, &dir, tolerance, false);
+        assert!(dir.join("square.png").exists());
+        check("square", &blue, &dir, tolerance, false);
+
+        let result = panic::catch_unwind(|| check("square", &red, &dir, tolerance, false));
+        assert!(result.is_err());
+        for kind in ["actual", "expected", "diff"] {
+            assert!(dir.join(format!("failures/square.{kind}.png")).exists());
+        }
+
+        // Blessing accepts the change
+        check("square", &red, &dir, tolerance, true);
+        check("square", &red, &dir, tolerance, false);
+        assert!(!dir.join("failures/square.diff.png").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
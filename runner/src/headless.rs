@@ -0,0 +1,383 @@
+//! Renders pages without a window or GPU. egui lays out and tessellates the page, the meshes
+//! are rasterized on the CPU.
+
+use crate::PHONE_SIZE;
+use egui::{
+    epaint::{textures::TexturesDelta, ClippedPrimitive, ImageData, Primitive, Vertex},
+    pos2, vec2, CentralPanel, Color32, Context, Frame, Pos2, RawInput, Rect, TextureId,
+};
+use image::RgbaImage;
+use rapp::{
+    backend::egui::EguiBackend,
+    navigation::{Navigator, Route},
+    screen::Screen,
+    semantics::SemanticTree,
+    theme::{Appearance, Theme},
+    Page, RustApp,
+};
+use std::collections::HashMap;
+
+/// The screen a page is rendered for
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Device {
+    /// In points
+    pub size: (f32, f32),
+    /// Pixels in the image for each point
+    pub pixels_per_point: f32,
+}
+
+impl Device {
+    /// The phone of the viewer
+    pub const PHONE: Device = Device {
+        size: PHONE_SIZE,
+        pixels_per_point: 2.,
+    };
+}
+
+impl Default for Device {
+    fn default() -> Self {
+        Self::PHONE
+    }
+}
+
+/// Renders the page of `route` the way it looks when it is the only page on the device, in
+/// the light theme of the app
+pub fn render_page_to_image<A: RustApp>(
+    app: &mut A,
+    route: &A::Route,
+    device: Device,
+) -> RgbaImage {
+    let mut page = app.page(route);
+    let theme = app.theme(Appearance::Light);
+    render_to_image(&mut *page, Navigator::new(route.clone()), &theme, device)
+}
+
+/// Renders a page that navigates with `navigator`, in `theme`
+pub fn render_to_image<R: Route>(
+    page: &mut dyn Page,
+    navigator: Navigator<R>,
+    theme: &Theme,
+    device: Device,
+) -> RgbaImage {
+    let ctx = Context::default();
+
+    // The second frame sees what egui remembered from the first, like the sizes of text edits
+    let mut textures = Textures::default();
+    let mut primitives = vec![];
+    for _ in 0..2 {
+        let output = ctx.run(input(device), |ctx| {
+            let frame = Frame::none().fill(theme.colors.background.into());
+            CentralPanel::default().frame(frame).show(ctx, |ui| {
+                let mut backend = EguiBackend::new(ui);
+                let mut screen = Screen::new(&mut backend, navigator.clone());
+                screen.set_theme(theme.clone());
+                page.show(&mut screen);
+                screen.draw();
+            });
+        });
+        textures.apply(output.textures_delta);
+        primitives = ctx.tessellate(output.shapes);
+    }
+
+    let mut canvas = Canvas::new(device);
+    for primitive in &primitives {
+        canvas.paint(primitive, &textures);
+    }
+    canvas.into_image()
+}
+
+/// The semantic tree of a page in `theme`, laid out on `device`
+pub fn page_semantics<R: Route>(
+    page: &mut dyn Page,
+    navigator: Navigator<R>,
+    theme: &Theme,
+    device: Device,
+) -> SemanticTree {
+    let ctx = Context::default();
+    let mut tree = None;
+    let _ = ctx.run(input(device), |ctx| {
+        CentralPanel::default()
+            .frame(Frame::none())
+            .show(ctx, |ui| {
+                let mut backend = EguiBackend::new(ui);
+                let mut screen = Screen::new(&mut backend, navigator);
+                screen.set_theme(theme.clone());
+                page.show(&mut screen);
+                tree = Some(screen.semantics());
+            });
+    });
+    tree.expect("the central panel is always shown")
+}
+
+fn input(device: Device) -> RawInput {
+    RawInput {
+        screen_rect: Some(Rect::from_min_size(
+            Pos2::ZERO,
+            vec2(device.size.0, device.size.1),
+        )),
+        pixels_per_point: Some(device.pixels_per_point),
+        time: Some(0.),
+        ..Default::default()
+    }
+}
+
+struct Texture {
+    size: [usize; 2],
+    // premultiplied
+    pixels: Vec<Color32>,
+}
+
+impl Texture {
+    /// Bilinear sample, premultiplied with components from 0 to 1
+    fn sample(&self, uv: Pos2) -> [f32; 4] {
+        let [width, height] = self.size;
+        let x = (uv.x * width as f32 - 0.5).clamp(0., (width - 1) as f32);
+        let y = (uv.y * height as f32 - 0.5).clamp(0., (height - 1) as f32);
+        let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+        let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+        let (fx, fy) = (x.fract(), y.fract());
+
+        let pixel = |x: usize, y: usize| rgba(self.pixels[y * width + x]);
+        let (a, b, c, d) = (pixel(x0, y0), pixel(x1, y0), pixel(x0, y1), pixel(x1, y1));
+        std::array::from_fn(|i| {
+            let top = a[i] + (b[i] - a[i]) * fx;
+            let bottom = c[i] + (d[i] - c[i]) * fx;
+            top + (bottom - top) * fy
+        })
+    }
+}
+
+/// The textures egui has set so far, like the font atlas
+#[derive(Default)]
+struct Textures(HashMap<TextureId, Texture>);
+
+impl Textures {
+    fn apply(&mut self, delta: TexturesDelta) {
+        for (id, image_delta) in delta.set {
+            let size = image_delta.image.size();
+            let pixels: Vec<Color32> = match image_delta.image {
+                ImageData::Color(image) => image.pixels,
+                ImageData::Font(image) => image.srgba_pixels(None).collect(),
+            };
+            match image_delta.pos {
+                None => {
+                    self.0.insert(id, Texture { size, pixels });
+                }
+                Some([left, top]) => {
+                    // A patch of a texture that is already there
+                    if let Some(texture) = self.0.get_mut(&id) {
+                        for (row, patch) in pixels.chunks_exact(size[0]).enumerate() {
+                            let start = (top + row) * texture.size[0] + left;
+                            texture.pixels[start..start + size[0]].copy_from_slice(patch);
+                        }
+                    }
+                }
+            }
+        }
+        for id in delta.free {
+            self.0.remove(&id);
+        }
+    }
+}
+
+/// Premultiplied pixels with components from 0 to 1
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels_per_point: f32,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Canvas {
+    fn new(device: Device) -> Self {
+        let width = (device.size.0 * device.pixels_per_point).round() as usize;
+        let height = (device.size.1 * device.pixels_per_point).round() as usize;
+        Self {
+            width,
+            height,
+            pixels_per_point: device.pixels_per_point,
+            pixels: vec![[0.; 4]; width * height],
+        }
+    }
+
+    fn paint(&mut self, primitive: &ClippedPrimitive, textures: &Textures) {
+        // Paint callbacks need a GPU
+        let Primitive::Mesh(mesh) = &primitive.primitive else {
+            return;
+        };
+        let ppp = self.pixels_per_point;
+        let clip = Rect::from_min_max(
+            pos2(
+                primitive.clip_rect.min.x * ppp,
+                primitive.clip_rect.min.y * ppp,
+            ),
+            pos2(
+                primitive.clip_rect.max.x * ppp,
+                primitive.clip_rect.max.y * ppp,
+            ),
+        )
+        .intersect(Rect::from_min_size(
+            Pos2::ZERO,
+            vec2(self.width as f32, self.height as f32),
+        ));
+        let texture = textures.0.get(&mesh.texture_id);
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertices = [0, 1, 2].map(|i| mesh.vertices[triangle[i] as usize]);
+            self.triangle(vertices, clip, texture);
+        }
+    }
+
+    fn triangle(&mut self, mut vertices: [Vertex; 3], clip: Rect, texture: Option<&Texture>) {
+        let ppp = self.pixels_per_point;
+        for vertex in &mut vertices {
+            vertex.pos = pos2(vertex.pos.x * ppp, vertex.pos.y * ppp);
+        }
+        let mut area = edge(vertices[0].pos, vertices[1].pos, vertices[2].pos);
+        if area == 0. {
+            return;
+        }
+        if area < 0. {
+            vertices.swap(1, 2);
+            area = -area;
+        }
+        let [p0, p1, p2] = vertices.map(|vertex| vertex.pos);
+
+        let bounds = Rect::from_min_max(p0.min(p1).min(p2), p0.max(p1).max(p2)).intersect(clip);
+        if !bounds.is_positive() {
+            return;
+        }
+        let (x_start, x_end) = (bounds.min.x.floor() as usize, bounds.max.x.ceil() as usize);
+        let (y_start, y_end) = (bounds.min.y.floor() as usize, bounds.max.y.ceil() as usize);
+
+        for y in y_start..y_end.min(self.height) {
+            for x in x_start..x_end.min(self.width) {
+                let p = pos2(x as f32 + 0.5, y as f32 + 0.5);
+                if !clip.contains(p) {
+                    continue;
+                }
+                let (Some(w0), Some(w1), Some(w2)) =
+                    (covers(p1, p2, p), covers(p2, p0, p), covers(p0, p1, p))
+                else {
+                    continue;
+                };
+                let weights = [w0 / area, w1 / area, w2 / area];
+
+                let colors = vertices.map(|vertex| rgba(vertex.color));
+                let mut color: [f32; 4] =
+                    std::array::from_fn(|i| (0..3).map(|v| colors[v][i] * weights[v]).sum::<f32>());
+                if let Some(texture) = texture {
+                    let uv = (0..3).fold(Pos2::ZERO, |uv, v| {
+                        uv + vertices[v].uv.to_vec2() * weights[v]
+                    });
+                    let texel = texture.sample(uv);
+                    for i in 0..4 {
+                        color[i] *= texel[i];
+                    }
+                }
+
+                // Premultiplied source over destination
+                let pixel = &mut self.pixels[y * self.width + x];
+                for i in 0..4 {
+                    pixel[i] = color[i] + pixel[i] * (1. - color[3]);
+                }
+            }
+        }
+    }
+
+    fn into_image(self) -> RgbaImage {
+        let bytes = self
+            .pixels
+            .iter()
+            .flat_map(|[r, g, b, a]| {
+                let unmultiply = |c: f32| if *a > 0. { c / a } else { 0. };
+                [unmultiply(*r), unmultiply(*g), unmultiply(*b), *a]
+                    .map(|c| (c * 255.).round().clamp(0., 255.) as u8)
+            })
+            .collect();
+        RgbaImage::from_raw(self.width as u32, self.height as u32, bytes)
+            .expect("the canvas has a pixel for every place in the image")
+    }
+}
+
+fn rgba(color: Color32) -> [f32; 4] {
+    color.to_array().map(|c| c as f32 / 255.)
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `p`
+fn edge(a: Pos2, b: Pos2, p: Pos2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+// The edge value when `p` is inside of the edge from `a` to `b`. A point exactly on an edge
+// shared by two triangles belongs to only one of them, so it is not painted twice.
+fn covers(a: Pos2, b: Pos2, p: Pos2) -> Option<f32> {
+    let value = edge(a, b, p);
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let inside = value > 0. || (value == 0. && (dy > 0. || (dy == 0. && dx < 0.)));
+    inside.then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_to_image, Device};
+    use rapp::{
+        drawables::traits::Sizing,
+        navigation::{Navigator, Route},
+        screen::Screen,
+        theme::Theme,
+        Page,
+    };
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct TestRoute;
+
+    impl Route for TestRoute {}
+
+    struct TestPage;
+
+    impl Page for TestPage {
+        fn name(&self) -> String {
+            "TestPage".to_string()
+        }
+
+        fn show(&mut self, screen: &mut Screen) {
+            screen.placeholder().width(50).height(50);
+            screen.text("Hello");
+        }
+    }
+
+    #[test]
+    fn renders_a_page() {
+        let navigator = Navigator::new(TestRoute);
+        let image = render_to_image(&mut TestPage, navigator, &Theme::light(), Device::PHONE);
+        assert_eq!(image.dimensions(), (400, 1000));
+
+        // The placeholder: 50 points is 100 pixels
+        let primary = Theme::light().colors.primary;
+        assert_eq!(
+            image.get_pixel(50, 50).0,
+            [primary.r, primary.g, primary.b, 255]
+        );
+        let background = *image.get_pixel(300, 900);
+        assert_eq!(background.0, [255, 255, 255, 255]);
+
+        // Something of the text, below the placeholder
+        let text = (0..200)
+            .flat_map(|x| (100..150).map(move |y| (x, y)))
+            .any(|(x, y)| *image.get_pixel(x, y) != background);
+        assert!(text);
+    }
+
+    #[test]
+    fn renders_in_the_theme() {
+        let theme = Theme::dark();
+        let navigator = Navigator::new(TestRoute);
+        let image = render_to_image(&mut TestPage, navigator, &theme, Device::PHONE);
+        let background = theme.colors.background;
+        assert_eq!(
+            image.get_pixel(300, 900).0,
+            [background.r, background.g, background.b, 255]
+        );
+    }
+}
@@ -2,51 +2,153 @@ use eframe::{
     egui::{self, Resize},
     epaint::{Color32, Stroke},
 };
-use egui::Ui;
-use rapp::{screen::Screen, PageFn, RustApp};
-use std::{cell::RefCell, vec};
+use egui::{Sense, Ui};
+use rapp::{
+    backend::egui::EguiBackend,
+    lifecycle::Lifecycle,
+    navigation::{Navigator, Route},
+    pages::PageCache,
+    screen::Screen,
+    store, task,
+    theme::{Appearance, Theme},
+    timer, Page, RustApp,
+};
+
+mod headless;
+
+pub use headless::{page_semantics, render_page_to_image, render_to_image, Device};
+pub use image::RgbaImage;
 
 const PHONE_SIZE: (f32, f32) = (200., 500.);
 const BOTTOM_HEIGHT: f32 = 30.;
 
 pub fn run(mut app: Box<impl RustApp + 'static>) -> eframe::Result<()> {
     app.start();
-    let eframe_app = EframeWrapped(app);
+    let navigator = Navigator::new(app.home());
+    let eframe_app = EframeWrapped {
+        app,
+        navigator,
+        pages: PageCache::new(),
+        lifecycle: Lifecycle::new(),
+        background: false,
+        appearance: Appearance::Light,
+        store_revision: store::revision(),
+    };
     let options = eframe::NativeOptions {
         initial_window_size: Some([1000., 800.].into()),
         ..Default::default()
     };
-    eframe::run_native("My egui App", options, Box::new(|_cc| Box::new(eframe_app)))
+    eframe::run_native(
+        "My egui App",
+        options,
+        Box::new(|cc| {
+            // Finished tasks draw again
+            let ctx = cc.egui_ctx.clone();
+            task::set_waker(move || ctx.request_repaint());
+            Box::new(eframe_app)
+        }),
+    )
 }
 
-struct EframeWrapped<'a>(Box<dyn RustApp + 'a>);
+struct EframeWrapped<A: RustApp> {
+    app: Box<A>,
+    navigator: Navigator<A::Route>,
+    // Kept between frames, so pages can hold state
+    pages: PageCache<A::Route>,
+    lifecycle: Lifecycle<A::Route>,
+    // The viewer sends the app to the background, as if the window lost the focus
+    background: bool,
+    // The viewer switches between the light and the dark theme of the app
+    appearance: Appearance,
+    // Of all stores, when the last frame ended
+    store_revision: u64,
+}
 
-impl eframe::App for EframeWrapped<'_> {
+impl<A: RustApp> eframe::App for EframeWrapped<A> {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // The current route is shown as well when it is not one of the preview routes
+        let mut routes = self.app.routes();
+        let current = self.navigator.current();
+        if !routes.contains(&current) {
+            routes.push(current);
+        }
+        let mut keep = routes.clone();
+        keep.extend(self.navigator.stack());
+        self.lifecycle
+            .retain(self.app.as_mut(), &mut self.pages, &keep);
+
+        // The first page appears before it is drawn
+        self.lifecycle
+            .navigated(self.app.as_mut(), &mut self.pages, &self.navigator);
+        let focused = ctx.input(|i| i.raw.has_focus);
+        self.lifecycle
+            .set_paused(self.app.as_mut(), self.background || !focused);
+
+        egui::TopBottomPanel::top("viewer").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.background, "Send app to background");
+                let mut dark = self.appearance == Appearance::Dark;
+                ui.checkbox(&mut dark, "Dark mode");
+                self.appearance = if dark {
+                    Appearance::Dark
+                } else {
+                    Appearance::Light
+                };
+            });
+        });
+        let theme = self.app.theme(self.appearance);
+
         egui::CentralPanel::default().show(ctx, |ui| {
             egui::ScrollArea::horizontal().show(ui, |ui| {
                 ui.horizontal(|ui| {
-                    for page in self.0.pages() {
-                        //let page = page.clone();
+                    for route in routes {
+                        let tasks = self.pages.tasks(self.app.as_mut(), &route);
+                        let page = self.pages.get(self.app.as_mut(), &route);
                         ui.vertical(|ui| {
-                            ui.push_id(page.name(), |ui| show_minified(page, self, ctx, ui))
+                            ui.push_id(format!("{route:?}"), |ui| {
+                                tasks.enter(|| {
+                                    show_minified(page, &route, &self.navigator, &theme, ctx, ui)
+                                })
+                            })
                         });
                     }
                 });
             });
         });
+
+        // Navigation requested while drawing takes effect in the next frame
+        if self.navigator.apply() {
+            self.lifecycle
+                .navigated(self.app.as_mut(), &mut self.pages, &self.navigator);
+            ctx.request_repaint();
+        }
+        // So do changes to the app and its stores, finished tasks and timers
+        let app_changed = self.app.frame_end();
+        let tasks_finished = task::poll();
+        let timers_fired = timer::fire_due();
+        if app_changed || tasks_finished || timers_fired {
+            ctx.request_repaint();
+        }
+        if let Some(due) = timer::next_due() {
+            ctx.request_repaint_after(due);
+        }
+        let store_revision = store::revision();
+        if store_revision != self.store_revision {
+            self.store_revision = store_revision;
+            ctx.request_repaint();
+        }
     }
 }
 
-fn show_minified(
-    mut page: PageFn,
-    wrapped_app: &mut EframeWrapped,
+fn show_minified<R: Route>(
+    page: &mut dyn Page,
+    route: &R,
+    navigator: &Navigator<R>,
+    theme: &Theme,
     ctx: &egui::Context,
     ui: &mut Ui,
 ) {
-    let app = &wrapped_app.0;
-
-    let active = app.current_page() == page.name();
+    let active = navigator.current() == *route;
 
     let stroke = if active {
         Stroke::new(2., Color32::LIGHT_GRAY)
@@ -59,8 +161,12 @@ fn show_minified(
         .outer_margin(25.)
         .stroke(Stroke::NONE)
         .show(ui, |ui| {
-            // page name
-            ui.label(egui::RichText::new(format!("{:?}", page.name())).size(10.));
+            // route - click it to navigate to the page
+            let name = egui::Label::new(egui::RichText::new(format!("{:?}", route)).size(10.))
+                .sense(Sense::click());
+            if ui.add(name).clicked() && !active {
+                navigator.push(route.clone());
+            }
             ui.end_row();
             ui.add_space(10.);
 
@@ -87,13 +193,20 @@ fn show_minified(
                                 //AppPage::get_page_fn(&p)(app, ctx, ui);
                                 ui.heading(page.name());
                                 ui.separator();
-                                let mut screen = Screen {
-                                    egui_ui: ui,
-                                    queue: RefCell::new(vec![]),
-                                };
-                                let s = &mut screen;
-                                page.show(s);
-                                s.draw();
+                                // the page gets the space between the heading and the bottom
+                                let used_height = ui.cursor().min.y - top_left.y;
+                                let page_size = egui::vec2(
+                                    ui.available_width(),
+                                    PHONE_SIZE.1 - used_height - BOTTOM_HEIGHT,
+                                );
+                                ui.allocate_ui(page_size, |ui| {
+                                    let mut backend = EguiBackend::new(ui);
+                                    let mut screen = Screen::new(&mut backend, navigator.clone());
+                                    screen.set_theme(theme.clone());
+                                    let s = &mut screen;
+                                    page.show(s);
+                                    s.draw();
+                                });
                                 {
                                     //let arc_ui = Arc::new(ui);
                                     //let screen = ScreenWithArc { arc_ui };
@@ -106,18 +219,17 @@ fn show_minified(
                                 let to_add = PHONE_SIZE.1 - used_height - BOTTOM_HEIGHT;
                                 ui.add_space(to_add);
 
-                                // // bottom
-                                // if let Some(bottom) = &p.template().map(|t| t.bottom()) {
-                                //     ui.separator();
-                                //     ui.horizontal(|ui| {
-                                //         if let Some(home) = bottom.home {
-                                //             if ui.small_button("[Home]").clicked() {
-                                //                 app.go_to(home);
-                                //             }
-                                //             ui.add_space(ui.available_width());
-                                //         }
-                                //     });
-                                // }
+                                // bottom
+                                ui.separator();
+                                ui.horizontal(|ui| {
+                                    if active
+                                        && navigator.can_pop()
+                                        && ui.small_button("[Back]").clicked()
+                                    {
+                                        navigator.pop();
+                                    }
+                                    ui.add_space(ui.available_width());
+                                });
                             });
                     });
                 });
@@ -1,33 +1,65 @@
-use rapp::{screen::Screen, Page, PageFn, RustApp};
+use rapp::{
+    color::Color,
+    drawables::{
+        text::{FontWeight, TextAlign},
+        traits::Sizing,
+    },
+    navigation::Route,
+    screen::Screen,
+    store::Store,
+    structures::{list::ListState, CrossAlign},
+    task, Page, PageFn, RustApp,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AppRoute {
+    Home,
+    Details { id: u64 },
+    Feed,
+}
+
+impl Route for AppRoute {}
 
 #[derive(Default)]
-pub struct App {}
+pub struct App {
+    // Number of items in the cart, shared by the pages
+    cart: Store<u32>,
+}
 
 impl RustApp for App {
-    fn pages(&mut self) -> Vec<PageFn> {
-        let mut pages = vec![];
-
-        let page1: PageFn = Box::new(HomePage {});
-        pages.push(page1);
+    type Route = AppRoute;
 
-        let page2: PageFn = Box::new(DetailsPage {});
-        pages.push(page2);
+    fn home(&self) -> AppRoute {
+        AppRoute::Home
+    }
 
-        pages
+    fn routes(&self) -> Vec<AppRoute> {
+        vec![AppRoute::Home, AppRoute::Details { id: 1 }, AppRoute::Feed]
     }
 
-    fn current_page(&self) -> String {
-        String::new()
+    fn page(&mut self, route: &AppRoute) -> PageFn {
+        match route {
+            AppRoute::Home => Box::new(HomePage {
+                cart: self.cart.clone(),
+            }),
+            AppRoute::Details { id } => Box::new(DetailsPage {
+                id: *id,
+                cart: self.cart.clone(),
+            }),
+            AppRoute::Feed => Box::new(FeedPage::default()),
+        }
     }
 
     fn start(&mut self) {}
 
     fn new() -> Self {
-        Self {}
+        Self::default()
     }
 }
 
-struct HomePage;
+struct HomePage {
+    cart: Store<u32>,
+}
 
 impl Page for HomePage {
     fn name(&self) -> String {
@@ -35,23 +67,221 @@ impl Page for HomePage {
     }
 
     fn show(&mut self, screen: &mut Screen) {
+        screen.text("Welcome").size(24).weight(FontWeight::Bold);
+        screen.text(format!("In cart: {}", self.cart.get()));
         screen.placeholder().height(150);
+        screen
+            .text(
+                "Rapp draws this text through the rapp api. Long text is cut off after two lines.",
+            )
+            .max_lines(2);
+
+        let navigator = screen.navigator::<AppRoute>();
+        screen
+            .row(|screen| {
+                screen
+                    .button("Open details")
+                    .on_click(move || navigator.push(AppRoute::Details { id: 2 }));
+                screen.placeholder().height(20).fill_width();
+            })
+            .spacing(5);
+
+        screen
+            .stack(|screen| {
+                screen.placeholder().height(60).fill_width();
+                screen.text("On top");
+            })
+            .align(CrossAlign::Center, CrossAlign::Center);
     }
 }
 
-struct DetailsPage;
+struct DetailsPage {
+    id: u64,
+    cart: Store<u32>,
+}
 
 impl Page for DetailsPage {
     fn name(&self) -> String {
-        "DetailsPage".to_string()
+        format!("DetailsPage {}", self.id)
     }
 
     fn show(&mut self, screen: &mut Screen) {
+        screen
+            .text(format!("Item {}", self.id))
+            .align(TextAlign::Center)
+            .selectable(true);
+        screen
+            .image(rapp::include_asset!("logo.svg"))
+            .height(40)
+            .description("Rapp logo");
         screen.placeholder().height(20).width(70);
         screen.placeholder();
 
         screen.separator().thickness(10);
         screen.separator();
         screen.separator().thickness(1).thickness(3).thickness(5);
+
+        let cart = self.cart.clone();
+        screen
+            .button("Add to cart")
+            .on_click(move || cart.update(|count| *count += 1));
+
+        let navigator = screen.navigator::<AppRoute>();
+        let home = navigator.clone();
+        screen
+            .button("Back (hold for home)")
+            .on_click(move || navigator.pop())
+            .on_long_press(move || home.reset(AppRoute::Home));
+        screen.button("Disabled").enabled(false);
+    }
+}
+
+struct FeedPage {
+    items: Store<Vec<String>>,
+    list: ListState,
+    // Number of times the feed was refreshed
+    refreshes: Store<u32>,
+}
+
+impl Default for FeedPage {
+    fn default() -> Self {
+        Self {
+            items: Store::new((1..=20).map(|item| format!("Item {item}")).collect()),
+            list: ListState::new(),
+            refreshes: Store::default(),
+        }
+    }
+}
+
+impl Page for FeedPage {
+    fn name(&self) -> String {
+        "FeedPage".to_string()
+    }
+
+    fn show(&mut self, screen: &mut Screen) {
+        screen.text("Pull down to refresh").weight(FontWeight::Bold);
+
+        let items = self.items.clone();
+        let keys = self.items.clone();
+        let (fresh, refreshes) = (self.items.clone(), self.refreshes.clone());
+        let (archived, deleted) = (self.items.clone(), self.items.clone());
+        let count = items.read().len();
+        screen
+            .list(&self.list, count, move |screen, index| {
+                screen.text(items.read()[index].clone());
+            })
+            .key(move |index| keys.read()[index].clone())
+            .spacing(8)
+            .on_refresh(move || {
+                refreshes.update(|count| *count += 1);
+                let item = format!("New {}", refreshes.get());
+                let fresh = fresh.clone();
+                task::spawn_blocking(move || item)
+                    .then(move |item| fresh.update(|items| items.insert(0, item.clone())))
+            })
+            .swipe_action("Archive", Color::GRAY, move |index| {
+                archived.update(|items| {
+                    let item = items.remove(index);
+                    items.push(item);
+                });
+            })
+            .swipe_action("Delete", Color::RED, move |index| {
+                deleted.update(|items| {
+                    items.remove(index);
+                });
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{App, AppRoute, DetailsPage, HomePage};
+    use rapp::{
+        navigation::Navigator,
+        semantics::{Role, SemanticTree},
+        store::Store,
+        theme::Theme,
+        RustApp,
+    };
+    use rapp_test::{assert_page_snapshot, page_semantics, Device, Driver, Find};
+
+    #[test]
+    fn snapshots() {
+        let cart = Store::new(3);
+        assert_page_snapshot!("home", HomePage { cart: cart.clone() }, AppRoute::Home);
+        assert_page_snapshot!(
+            "details",
+            DetailsPage { id: 1, cart },
+            AppRoute::Details { id: 1 }
+        );
+    }
+
+    #[test]
+    fn details_can_go_back() {
+        let navigator = Navigator::new(AppRoute::Details { id: 3 });
+        let mut page = DetailsPage {
+            id: 3,
+            cart: Store::default(),
+        };
+        let tree = page_semantics(&mut page, navigator, &Theme::light(), Device::PHONE);
+
+        assert!(tree.find_by_text("Item 3").is_some());
+        let back = tree.find_by_text("Back (hold for home)").unwrap();
+        assert_eq!(back.role, Role::Button);
+        assert!(back.enabled);
+        assert!(!tree.find_by_text("Disabled").unwrap().enabled);
+    }
+
+    #[test]
+    fn open_details_and_go_home() {
+        let mut driver = Driver::new(App::new());
+        assert_eq!(driver.current_page(), AppRoute::Home);
+
+        driver.tap(Find::Text("Open details"));
+        assert_eq!(driver.current_page(), AppRoute::Details { id: 2 });
+        assert!(driver.find(Find::Text("Item 2")).is_some());
+
+        // The logo takes its width once it is decoded
+        let logo = driver.find(Find::Text("Rapp logo")).unwrap();
+        assert_eq!(logo.bounds.width, 0.);
+        driver.run_tasks();
+        let logo = driver.find(Find::Text("Rapp logo")).unwrap();
+        assert_eq!(logo.bounds.width, 40.);
+
+        driver.tap(Find::Text("Add to cart"));
+        driver.tap(Find::Text("Add to cart"));
+
+        driver.long_press(Find::Text("Back (hold for home)"));
+        assert_eq!(driver.current_page(), AppRoute::Home);
+        assert!(!driver.navigator().can_pop());
+        assert!(driver.find(Find::Text("In cart: 2")).is_some());
+    }
+
+    fn spinner_shown(tree: &SemanticTree) -> bool {
+        tree.iter().any(|node| node.role == Role::Spinner)
+    }
+
+    #[test]
+    fn pull_to_refresh_and_swipe_to_delete() {
+        let mut driver = Driver::new(App::new());
+        driver.navigator().push(AppRoute::Feed);
+        driver.frame();
+        assert!(driver.find(Find::Text("Item 1")).is_some());
+
+        // Not pulled far enough
+        driver.drag(Find::Text("Item 1"), (0., 40.));
+        assert!(!spinner_shown(driver.semantics()));
+        driver.drag(Find::Text("Item 1"), (0., 100.));
+        assert!(spinner_shown(driver.semantics()));
+        driver.run_tasks();
+        assert!(!spinner_shown(driver.semantics()));
+        assert!(driver.find(Find::Text("New 1")).is_some());
+
+        driver.drag(Find::Text("Item 2"), (-150., 0.));
+        assert!(driver.find(Find::Text("Archive")).is_some());
+        driver.tap(Find::Text("Delete"));
+        assert!(driver.find(Find::Text("Item 2")).is_none());
+        assert!(driver.find(Find::Text("Delete")).is_none());
+        assert!(driver.find(Find::Text("Item 3")).is_some());
     }
 }